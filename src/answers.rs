@@ -0,0 +1,217 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+use crate::error::{AocError, Result};
+use crate::input::Source;
+use crate::solution::Answer;
+
+/// The answers file used when none is given: `answers.toml` next to this crate's `Cargo.toml`.
+pub fn default_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.toml")
+}
+
+/// A hash identifying a puzzle input, so answers recorded for one input are never compared against
+/// another.  This is 64-bit FNV-1a over the input with `\r\n` line endings read as `\n`, written as
+/// 16 hex digits; it's stable across platforms and compiler versions, unlike `DefaultHasher`.
+pub fn input_hash(input: &str) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let bytes = input.as_bytes();
+    for (i, &byte) in bytes.iter().enumerate() {
+        if byte == b'\r' && bytes.get(i + 1) == Some(&b'\n') {
+            continue;
+        }
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// How an answer compares with the one on record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    /// It matches the recorded answer.
+    Pass,
+    /// It differs from the recorded answer, `expected`.
+    Fail { expected: String },
+    /// Nothing is recorded for this day, part and input.
+    New,
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Check::Pass => write!(f, "PASS"),
+            Check::Fail { expected } => write!(f, "FAIL (expected {})", expected),
+            Check::New => write!(f, "NEW"),
+        }
+    }
+}
+
+/// Known-good answers, keyed by day, input hash and part.
+///
+/// They're kept in a small subset of TOML: one `[dayN.<input hash>]` table per input, holding
+/// `partN = "answer"` keys.  Comments and blank lines are allowed, and bare integers are accepted
+/// as answers too.
+///
+/// ```text
+/// [day3.49bb0b6817dc2740]
+/// part1 = "4361"
+/// part2 = "467835"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Answers {
+    answers: BTreeMap<(u8, String), BTreeMap<u8, String>>,
+}
+
+impl Answers {
+    /// The answer recorded for `part` of `day` with the input hashed to `hash`.
+    pub fn get(&self, day: u8, hash: &str, part: u8) -> Option<&str> {
+        self.answers
+            .get(&(day, hash.to_string()))
+            .and_then(|parts| parts.get(&part))
+            .map(String::as_str)
+    }
+
+    /// Records `answer` as correct, replacing anything recorded before.
+    pub fn insert(&mut self, day: u8, hash: &str, part: u8, answer: &Answer) {
+        self.answers
+            .entry((day, hash.to_string()))
+            .or_default()
+            .insert(part, answer.to_string());
+    }
+
+    /// Compares `answer` with the one on record.
+    pub fn check(&self, day: u8, hash: &str, part: u8, answer: &Answer) -> Check {
+        match self.get(day, hash, part) {
+            None => Check::New,
+            Some(expected) if expected == answer.to_string() => Check::Pass,
+            Some(expected) => Check::Fail { expected: expected.to_string() },
+        }
+    }
+
+    /// Parses the TOML subset described on [`Answers`].
+    pub fn parse(toml: &str) -> Result<Answers> {
+        let mut answers = Answers::default();
+        let mut table: Option<(u8, String)> = None;
+
+        for (line_num, line) in toml.lines().enumerate() {
+            let line_num = line_num + 1;
+            let entry = line.split('#').next().unwrap_or_default().trim();
+            if entry.is_empty() {
+                continue;
+            }
+
+            if let Some(header) = entry.strip_prefix('[') {
+                let (day, hash) = header
+                    .strip_suffix(']')
+                    .and_then(|header| header.trim().strip_prefix("day"))
+                    .and_then(|header| header.split_once('.'))
+                    .ok_or_else(|| AocError::parse_at(line_num, line, entry, format!("expected `[dayN.<input hash>]`, found `{}`", entry)))?;
+                let day = day
+                    .parse()
+                    .map_err(|err| AocError::parse_at(line_num, line, day, format!("invalid day `{}`: {}", day, err)))?;
+                if hash.is_empty() || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(AocError::parse_at(line_num, line, hash, format!("invalid input hash `{}`", hash)));
+                }
+                table = Some((day, hash.to_ascii_lowercase()));
+                continue;
+            }
+
+            let (key, value) = entry
+                .split_once('=')
+                .ok_or_else(|| AocError::parse_at(line_num, line, entry, format!("expected `partN = \"answer\"`, found `{}`", entry)))?;
+            let (key, value) = (key.trim(), value.trim());
+            let part = match key {
+                "part1" => 1,
+                "part2" => 2,
+                _ => return Err(AocError::parse_at(line_num, line, key, format!("unknown key `{}`; expected part1 or part2", key))),
+            };
+            let answer = match value.strip_prefix('"').and_then(|value| value.strip_suffix('"')) {
+                Some(answer) if !answer.contains(['"', '\\']) => answer,
+                Some(_) => return Err(AocError::parse_at(line_num, line, value, "escapes aren't supported in answers")),
+                None if !value.is_empty() && value.chars().all(|c| c.is_ascii_digit()) => value,
+                None => return Err(AocError::parse_at(line_num, line, value, format!("expected a quoted answer, found `{}`", value))),
+            };
+            let table = table
+                .clone()
+                .ok_or_else(|| AocError::parse(line_num, 1, "expected a `[dayN.<input hash>]` table before its answers"))?;
+            answers.answers.entry(table).or_default().insert(part, answer.to_string());
+        }
+
+        Ok(answers)
+    }
+
+    /// Reads the answers at `path`.  A missing file has no answers.
+    pub fn load(path: &Path) -> Result<Answers> {
+        match fs::read_to_string(path) {
+            Ok(toml) => Answers::parse(&toml),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(error) => Err(AocError::Io { input: Source::File(path.to_path_buf()), error }),
+        }
+    }
+
+    /// Writes the answers to `path`.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_string()).map_err(|error| AocError::Io { input: Source::File(path.to_path_buf()), error })
+    }
+}
+
+/// Writes the answers in the TOML subset [`Answers::parse`] reads, ordered by day.
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# Known answers for each day and input, checked by the advent_of_code runner.")?;
+        for ((day, hash), parts) in &self.answers {
+            writeln!(f, "\n[day{}.{}]", day, hash)?;
+            for (part, answer) in parts {
+                writeln!(f, "part{} = \"{}\"", part, answer)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_round_trip() {
+        let mut answers = Answers::default();
+        answers.insert(3, "00ff", 1, &Answer::Number(4361));
+        answers.insert(3, "00ff", 2, &Answer::Number(467835));
+        answers.insert(1, "abcd", 2, &Answer::Number(281));
+
+        let toml = answers.to_string();
+        assert!(toml.contains("[day1.abcd]\npart2 = \"281\"\n\n[day3.00ff]\npart1 = \"4361\"\n"), "{}", toml);
+        assert_eq!(Answers::parse(&toml).unwrap(), answers);
+    }
+
+    #[test]
+    fn answers_are_checked_per_input() {
+        let answers = Answers::parse("[day6.abc]  # the example\npart1 = 288\n").unwrap();
+        assert_eq!(answers.check(6, "abc", 1, &Answer::Number(288)), Check::Pass);
+        assert_eq!(
+            answers.check(6, "abc", 1, &Answer::Number(289)),
+            Check::Fail { expected: "288".to_string() },
+        );
+        assert_eq!(answers.check(6, "abc", 2, &Answer::Number(71503)), Check::New);
+        assert_eq!(answers.check(6, "def", 1, &Answer::Number(288)), Check::New);
+    }
+
+    #[test]
+    fn malformed_answers_are_rejected() {
+        let err = Answers::parse("part1 = \"1\"").unwrap_err();
+        assert!(matches!(err, AocError::Parse { line: 1, column: 1, .. }), "{}", err);
+        let err = Answers::parse("[day1.ab]\npart3 = \"1\"").unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 1: unknown key `part3`; expected part1 or part2");
+        assert!(Answers::parse("[day1.xyz]").is_err());
+    }
+
+    #[test]
+    fn input_hash_ignores_line_endings() {
+        assert_eq!(input_hash("a\r\nb\r\n"), input_hash("a\nb\n"));
+        assert_ne!(input_hash("a\nb\n"), input_hash("a\nc\n"));
+        assert_eq!(input_hash(""), "cbf29ce484222325");
+    }
+}
@@ -0,0 +1,19 @@
+use std::{env, process};
+use advent_of_code::day05::seed_fertilizer::{part_1, part_2};
+use advent_of_code::input;
+
+fn main() {
+    let input = input::load(5, env::args().nth(1).as_deref()).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1);
+    });
+
+    match part_1(&input) {
+        Ok(answer) => println!("Part 1:\t{}", answer),
+        Err(err) => eprintln!("Part 1:\t{}", err),
+    }
+    match part_2(&input) {
+        Ok(answer) => println!("Part 2:\t{}", answer),
+        Err(err) => eprintln!("Part 2:\t{}", err),
+    }
+}
@@ -1,142 +1,109 @@
-use std::collections::{HashMap, VecDeque};
-use std::env;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::{env, process};
+use advent_of_code::day01::trebuchet::{calibration_sum, explain, part_one, DigitTokenizer, Vocabulary, ENGLISH_DIGITS};
+use advent_of_code::input;
 
-fn main() {
-    let file_path = env::current_dir()
-        .expect("Unable to open cwd")
-        .join("assets")
-        .join("day_1_input.txt");
+const USAGE: &str = "Usage: trebuchet [INPUT] [--words <LANGUAGE|FILE>] [--word <WORD=DIGIT>]... [--explain | --json]
 
-    let file1 = File::open(&file_path).expect("Unable to open file");
-    let file2 = File::open(&file_path).expect("Unable to open file");
+--words picks the digit words part two recognises: one of english (the default),
+german, french, spanish or none, or the path of a vocabulary file.  --word adds a
+single extra word, e.g. --word zero=0.
 
-    let reader1 = BufReader::new(file1);
-    let reader2 = BufReader::new(file2);
+--explain prints how each line's calibration value was found with those words:
+line number, value, first and last digit, then every digit found as TEXT@OFFSET.
+--json prints the same as one JSON object per line.  Use --words none to explain
+part one.";
 
-    let part_one_sum = part_one(reader1);
-    let part_two_sum = part_two(reader2);
+/// How `--explain` reports are printed.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Explain {
+    Table,
+    Json,
+}
 
-    println!("Part One Solution:\t{}", part_one_sum);
-    println!("Part Two Solution:\t{}", part_two_sum);
+/// The command line.
+struct Args {
+    input: Option<String>,
+    /// The digit words for part two.
+    vocabulary: Vocabulary,
+    explain: Option<Explain>,
 }
 
-/// # Day 1: Trebuchet?!
-/// Something is wrong with global snow production, and you've been selected to take a look.
-/// The Elves have even given you a map; on it, they've used stars to mark the top fifty locations
-/// that are likely to be having problems.
-///
-/// You've been doing this long enough to know that to restore snow operations, you need to check
-/// all *fifty stars* by December 25th.
-///
-/// Collect stars by solving puzzles.  Two puzzles will be made available on each day in the Advent
-/// Calendar; the second puzzle is unlocked when you complete the first. Each puzzle grants *one star*.
-/// Good luck!
-///
-/// You try to ask why they can't just use a weather machine ("not powerful enough") and where
-/// they're even sending you ("the sky") and why your map looks mostly blank ("you sure ask a lot of
-/// questions") and hang on did you say the sky ("of course, where do you think snow comes from")
-/// when you realize that the Elves are already loading you into a trebuchet ("please hold still, we
-/// need to strap you in").
-///
-/// As they're making the final adjustments, they discover that their calibration document (your
-/// puzzle input) has been *amended* by a very young Elf who was apparently just excited to show off
-/// her art skills. Consequently, the Elves are having trouble reading the values on the document.
-///
-/// The newly-improved calibration document consists of lines of text; each line originally contained
-/// a specific *calibration value* that the Elves now need to recover. On each line, the calibration
-/// value can be found by combining the first digit and the last digit (in that order) to form a
-/// single *two-digit number*.
-///
-/// # Example
-///
-/// ```
-/// 1abc2
-/// pqr3stu8vwx
-/// a1b2c3d4e5f
-/// treb7uchet
-/// ```
-///
-/// In this example, the calibration values for these four lines are `12`, `38`, `15`, and `17`.
-/// Adding these together produces `142`.
-///
-/// Consider your entire calibration document. *What is the sum of all of the calibration values?*
-fn part_one(reader: BufReader<File>) -> usize {
-    reader.lines()
-        .filter_map(|line| line.ok())
-        .filter_map(|line| {
-            let mut char_nums: VecDeque<_> = line.split(|c: char| !c.is_digit(10))
-                .filter(|s| !s.is_empty())
-                .flat_map(|s| s.chars())
-                .collect();
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut input = None;
+    let mut vocabulary = None;
+    let mut extra_words = Vec::new();
+    let mut explain = None;
 
-            let first = char_nums.pop_front().unwrap_or_default();
-            let last = char_nums.pop_back().unwrap_or(first);
-            let concatenated = format!("{}{}", first, last);
-            concatenated.parse::<usize>().ok()
-        }).sum()
-}
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--words" | "-w" => {
+                let value = args.next().ok_or("--words requires a value")?;
+                vocabulary = Some(match Vocabulary::named(&value) {
+                    Some(vocabulary) => vocabulary,
+                    None => Vocabulary::load(&value).map_err(|err| err.to_string())?,
+                });
+            }
+            "--word" => {
+                let value = args.next().ok_or("--word requires a value")?;
+                let (word, digit) = value
+                    .split_once('=')
+                    .and_then(|(word, digit)| Some((word, digit.parse::<u8>().ok().filter(|&d| d <= 9)?)))
+                    .ok_or_else(|| format!("invalid word `{}`; expected WORD=DIGIT", value))?;
+                if word.trim().is_empty() {
+                    return Err(format!("missing word before `=` in `{}`", value));
+                }
+                extra_words.push((word.to_string(), digit));
+            }
+            "--explain" => explain = Some(Explain::Table),
+            "--json" => explain = Some(Explain::Json),
+            _ if input.is_none() && !arg.starts_with("--") => input = Some(arg),
+            _ => return Err(format!("unexpected argument `{}`", arg)),
+        }
+    }
 
-/// Your calculation isn't quite right. It looks like some of the digits are actually *spelled out
-/// with letters:* `one`, `two`, `three`, `four`, `five`, `six`, `seven`, `eight`, and `nine` *also*
-/// count as valid "digits".
-///
-/// Equipped with this new information, you now need to find the real first and last digit on each
-/// line.
-///
-/// # Example
-///
-/// ```
-/// two1nine
-/// eightwothree
-/// abcone2threexyz
-/// xtwone3four
-/// 4nineeightseven2
-/// zoneight234
-/// 7pqrstsixteen
-/// ```
-///
-/// In this example, the calibration values are `29`, `83`, `13`, `24`, `42`, `14`, and `76`. Adding
-/// these together produces `281`.
-///
-/// *What is the sum of all of the calibration values?*
-fn part_two(reader: BufReader<File>) -> usize {
-    let map_nums = vec![
-        ("one", '1'),
-        ("two", '2'),
-        ("three", '3'),
-        ("four", '4'),
-        ("five", '5'),
-        ("six", '6'),
-        ("seven", '7'),
-        ("eight", '8'),
-        ("nine", '9'),
-    ];
-    let mut num_map: HashMap<&str, char> = HashMap::new();
-    for (k, v) in map_nums.into_iter() {
-        num_map.insert(k, v);
+    let mut vocabulary = vocabulary.unwrap_or_else(|| Vocabulary::from_words(&ENGLISH_DIGITS));
+    for (word, digit) in extra_words {
+        vocabulary = vocabulary.with_word(word, digit);
     }
+    Ok(Args { input, vocabulary, explain })
+}
 
-    reader
-        .lines()
-        .filter_map(|line| line.ok())
-        .filter_map(|line| {
-            let mut v: VecDeque<char> = VecDeque::new();
-            for (i, c) in line.char_indices() {
-                if c.is_digit(10) {
-                    v.push_back(c);
-                } else if let substring = &line[i..line.len()] {
-                    for (key, val) in num_map.iter() {
-                        if substring.starts_with(key) {
-                            v.push_back(*val);
-                        }
-                    }
-                }
+fn main() {
+    let args = parse_args(env::args().skip(1)).unwrap_or_else(|err| {
+        eprintln!("error: {}\n\n{}", err, USAGE);
+        process::exit(2);
+    });
+
+    let input = input::load(1, args.input.as_deref()).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1);
+    });
+    let tokenizer = DigitTokenizer::with_vocabulary(&args.vocabulary);
+
+    if let Some(format) = args.explain {
+        if format == Explain::Table {
+            println!("line\tvalue\tfirst\tlast\tdigits");
+        }
+        let mut sum = 0;
+        for report in explain(&input, &tokenizer) {
+            sum += report.value().unwrap_or(0);
+            match format {
+                Explain::Table => println!("{}", report),
+                Explain::Json => println!("{}", report.to_json()),
             }
-            let first = v.pop_front().unwrap_or_default();
-            let last = v.pop_back().unwrap_or(first);
-            let concatenated = format!("{}{}", first, last);
-            concatenated.parse::<usize>().ok()
-        }).sum()
-}
\ No newline at end of file
+        }
+        if format == Explain::Table {
+            println!("sum\t{}", sum);
+        }
+        return;
+    }
+
+    match part_one(&input) {
+        Ok(answer) => println!("Part One Solution:\t{}", answer),
+        Err(err) => eprintln!("Part One Solution:\t{}", err),
+    }
+    match calibration_sum(&input, &tokenizer) {
+        Ok(answer) => println!("Part Two Solution:\t{}", answer),
+        Err(err) => eprintln!("Part Two Solution:\t{}", err),
+    }
+}
@@ -0,0 +1,5 @@
+//! Day 1: Trebuchet?!
+
+pub mod trebuchet;
+
+pub use trebuchet::Trebuchet;
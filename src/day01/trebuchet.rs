@@ -0,0 +1,623 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use crate::error::{AocError, Result};
+use crate::input::Source;
+use crate::solution::{Answer, Solution};
+
+/// Day 1: Trebuchet?!
+pub struct Trebuchet;
+
+impl Solution for Trebuchet {
+    const DAY: u8 = 1;
+    const NAME: &'static str = "trebuchet";
+
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.to_string())
+    }
+
+    fn part_1(input: &Self::Input) -> Result<Answer> {
+        part_one(input).map(Answer::from)
+    }
+
+    fn part_2(input: &Self::Input) -> Result<Answer> {
+        part_two(input).map(Answer::from)
+    }
+}
+
+/// # Day 1: Trebuchet?!
+/// Something is wrong with global snow production, and you've been selected to take a look.
+/// The Elves have even given you a map; on it, they've used stars to mark the top fifty locations
+/// that are likely to be having problems.
+///
+/// You've been doing this long enough to know that to restore snow operations, you need to check
+/// all *fifty stars* by December 25th.
+///
+/// Collect stars by solving puzzles.  Two puzzles will be made available on each day in the Advent
+/// Calendar; the second puzzle is unlocked when you complete the first. Each puzzle grants *one star*.
+/// Good luck!
+///
+/// You try to ask why they can't just use a weather machine ("not powerful enough") and where
+/// they're even sending you ("the sky") and why your map looks mostly blank ("you sure ask a lot of
+/// questions") and hang on did you say the sky ("of course, where do you think snow comes from")
+/// when you realize that the Elves are already loading you into a trebuchet ("please hold still, we
+/// need to strap you in").
+///
+/// As they're making the final adjustments, they discover that their calibration document (your
+/// puzzle input) has been *amended* by a very young Elf who was apparently just excited to show off
+/// her art skills. Consequently, the Elves are having trouble reading the values on the document.
+///
+/// The newly-improved calibration document consists of lines of text; each line originally contained
+/// a specific *calibration value* that the Elves now need to recover. On each line, the calibration
+/// value can be found by combining the first digit and the last digit (in that order) to form a
+/// single *two-digit number*.
+///
+/// # Example
+///
+/// ```text
+/// 1abc2
+/// pqr3stu8vwx
+/// a1b2c3d4e5f
+/// treb7uchet
+/// ```
+///
+/// In this example, the calibration values for these four lines are `12`, `38`, `15`, and `17`.
+/// Adding these together produces `142`.
+///
+/// Consider your entire calibration document. *What is the sum of all of the calibration values?*
+pub fn part_one(input: &str) -> Result<usize> {
+    calibration_sum(input, &DigitTokenizer::numerals())
+}
+
+/// Your calculation isn't quite right. It looks like some of the digits are actually *spelled out
+/// with letters:* `one`, `two`, `three`, `four`, `five`, `six`, `seven`, `eight`, and `nine` *also*
+/// count as valid "digits".
+///
+/// Equipped with this new information, you now need to find the real first and last digit on each
+/// line.
+///
+/// # Example
+///
+/// ```text
+/// two1nine
+/// eightwothree
+/// abcone2threexyz
+/// xtwone3four
+/// 4nineeightseven2
+/// zoneight234
+/// 7pqrstsixteen
+/// ```
+///
+/// In this example, the calibration values are `29`, `83`, `13`, `24`, `42`, `14`, and `76`. Adding
+/// these together produces `281`.
+///
+/// *What is the sum of all of the calibration values?*
+pub fn part_two(input: &str) -> Result<usize> {
+    calibration_sum(input, &DigitTokenizer::english())
+}
+
+/// The sum of the calibration values of every line of `input`.  A line without any digit has no
+/// calibration value and is an error; [`explain`] shows which lines those are.
+pub fn calibration_sum(input: &str, tokenizer: &DigitTokenizer) -> Result<usize> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(line_num, line)| {
+            tokenizer
+                .calibration_value(line)
+                .ok_or_else(|| AocError::parse(line_num + 1, 1, "no digit on line"))
+        })
+        .sum()
+}
+
+/// A digit found in a calibration line: `text` is the numeral or word, starting at byte `offset`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DigitToken<'a> {
+    pub offset: usize,
+    pub text: &'a str,
+    pub digit: u8,
+}
+
+/// How the calibration value of a single line was worked out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineReport<'a> {
+    /// 1-based line number within the input.
+    pub line: usize,
+    /// Every digit found on the line.
+    pub tokens: Vec<DigitToken<'a>>,
+    pub first: Option<DigitToken<'a>>,
+    pub last: Option<DigitToken<'a>>,
+}
+
+impl LineReport<'_> {
+    /// The line's calibration value, or `None` if no digit was found on it.
+    pub fn value(&self) -> Option<usize> {
+        Some(self.first?.digit as usize * 10 + self.last?.digit as usize)
+    }
+
+    /// The report as a single line of JSON.
+    pub fn to_json(&self) -> String {
+        let token = |token: &DigitToken| {
+            format!(r#"{{"digit":{},"offset":{},"text":{}}}"#, token.digit, token.offset, json_string(token.text))
+        };
+        let optional = |t: &Option<DigitToken>| t.as_ref().map_or("null".to_string(), token);
+        let tokens: Vec<String> = self.tokens.iter().map(token).collect();
+
+        format!(
+            r#"{{"line":{},"value":{},"first":{},"last":{},"tokens":[{}],"no_digits":{}}}"#,
+            self.line,
+            self.value().map_or("null".to_string(), |value| value.to_string()),
+            optional(&self.first),
+            optional(&self.last),
+            tokens.join(","),
+            self.value().is_none(),
+        )
+    }
+}
+
+/// One row of a table of reports: line number, value, first and last digit, then every digit
+/// found, each written as `text@offset`.
+impl fmt::Display for LineReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let token = |t: &Option<DigitToken>| t.map_or("-".to_string(), |t| format!("{}@{}", t.text, t.offset));
+        let value = self.value().map_or("-".to_string(), |value| value.to_string());
+        write!(f, "{}\t{}\t{}\t{}\t", self.line, value, token(&self.first), token(&self.last))?;
+        if self.tokens.is_empty() {
+            return write!(f, "NO DIGITS");
+        }
+        let tokens: Vec<String> = self.tokens.iter().map(|t| format!("{}@{}", t.text, t.offset)).collect();
+        write!(f, "{}", tokens.join(" "))
+    }
+}
+
+/// Works out the calibration value of every line of `input`, keeping track of how each one was
+/// found.  Lines without any digits are included, with no value.
+pub fn explain<'a>(input: &'a str, tokenizer: &'a DigitTokenizer) -> impl Iterator<Item = LineReport<'a>> + 'a {
+    input.lines().enumerate().map(move |(line_num, line)| LineReport {
+        line: line_num + 1,
+        tokens: tokenizer.tokens(line).collect(),
+        first: tokenizer.first_token(line),
+        last: tokenizer.last_token(line),
+    })
+}
+
+/// `s` as a quoted JSON string.
+fn json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// The digit words recognised by part two.
+pub const ENGLISH_DIGITS: [(&str, u8); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+/// The German digit words, for `--words german`.
+pub const GERMAN_DIGITS: [(&str, u8); 9] = [
+    ("eins", 1),
+    ("zwei", 2),
+    ("drei", 3),
+    ("vier", 4),
+    ("fünf", 5),
+    ("sechs", 6),
+    ("sieben", 7),
+    ("acht", 8),
+    ("neun", 9),
+];
+
+/// The French digit words, for `--words french`.
+pub const FRENCH_DIGITS: [(&str, u8); 9] = [
+    ("un", 1),
+    ("deux", 2),
+    ("trois", 3),
+    ("quatre", 4),
+    ("cinq", 5),
+    ("six", 6),
+    ("sept", 7),
+    ("huit", 8),
+    ("neuf", 9),
+];
+
+/// The Spanish digit words, for `--words spanish`.
+pub const SPANISH_DIGITS: [(&str, u8); 9] = [
+    ("uno", 1),
+    ("dos", 2),
+    ("tres", 3),
+    ("cuatro", 4),
+    ("cinco", 5),
+    ("seis", 6),
+    ("siete", 7),
+    ("ocho", 8),
+    ("nueve", 9),
+];
+
+/// The words a [`DigitTokenizer`] should read as digits, on top of the numerals themselves.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Vocabulary {
+    words: Vec<(String, u8)>,
+}
+
+impl Vocabulary {
+    /// Names accepted by [`Vocabulary::named`].
+    pub const NAMES: [&'static str; 5] = ["english", "german", "french", "spanish", "none"];
+
+    /// One of the built-in word tables: `english`, `german`, `french`, `spanish`, or `none` for
+    /// numerals only.
+    pub fn named(name: &str) -> Option<Vocabulary> {
+        let words: &[(&str, u8)] = match name {
+            "english" | "en" => &ENGLISH_DIGITS,
+            "german" | "de" => &GERMAN_DIGITS,
+            "french" | "fr" => &FRENCH_DIGITS,
+            "spanish" | "es" => &SPANISH_DIGITS,
+            "none" => &[],
+            _ => return None,
+        };
+        Some(Vocabulary::from_words(words))
+    }
+
+    pub fn from_words(words: &[(&str, u8)]) -> Vocabulary {
+        Vocabulary { words: words.iter().map(|&(word, digit)| (word.to_string(), digit)).collect() }
+    }
+
+    /// Adds `word` as another way of writing `digit`, e.g. `("zero", 0)`.
+    pub fn with_word(mut self, word: impl Into<String>, digit: u8) -> Vocabulary {
+        self.words.push((word.into(), digit));
+        self
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = (&str, u8)> {
+        self.words.iter().map(|(word, digit)| (word.as_str(), *digit))
+    }
+
+    /// Parses a vocabulary file.  Each non-blank line is either `word = digit`, adding a word, or
+    /// `language = <name>`, adding every word of a built-in table.  `#` starts a comment.
+    ///
+    /// ```text
+    /// language = english
+    /// zero = 0
+    /// ```
+    pub fn parse(config: &str) -> Result<Vocabulary> {
+        let mut vocabulary = Vocabulary::default();
+
+        for (line_num, line) in config.lines().enumerate() {
+            let line_num = line_num + 1;
+            let entry = line.split('#').next().unwrap_or_default().trim();
+            if entry.is_empty() {
+                continue;
+            }
+            let (key, value) = entry
+                .split_once('=')
+                .map(|(key, value)| (key.trim(), value.trim()))
+                .ok_or_else(|| AocError::parse_at(line_num, line, entry, "expected `word = digit`"))?;
+
+            if key == "language" {
+                let table = Vocabulary::named(value).ok_or_else(|| {
+                    AocError::parse_at(
+                        line_num,
+                        line,
+                        value,
+                        format!("unknown language `{}`; expected one of {}", value, Vocabulary::NAMES.join(", ")),
+                    )
+                })?;
+                vocabulary.words.extend(table.words);
+                continue;
+            }
+            if key.is_empty() {
+                return Err(AocError::parse_at(line_num, line, entry, "missing word before `=`"));
+            }
+            let digit = match value.parse::<u8>() {
+                Ok(digit) if digit <= 9 => digit,
+                _ => {
+                    return Err(AocError::parse_at(
+                        line_num,
+                        line,
+                        value,
+                        format!("expected a digit from 0 to 9, found `{}`", value),
+                    ))
+                }
+            };
+            vocabulary.words.push((key.to_string(), digit));
+        }
+
+        Ok(vocabulary)
+    }
+
+    /// Reads and parses the vocabulary file at `path`; see [`Vocabulary::parse`].
+    pub fn load(path: impl AsRef<Path>) -> Result<Vocabulary> {
+        let path = path.as_ref();
+        let config = fs::read_to_string(path)
+            .map_err(|error| AocError::Io { input: Source::File(path.to_path_buf()), error })?;
+        Vocabulary::parse(&config)
+    }
+}
+
+/// Finds the digits in a calibration line, whether written as numerals or spelled out as words.
+///
+/// Words are matched with a pair of tries, one holding each word forwards and one backwards, so the
+/// first digit is found in a single pass from the start of the line and the last digit in a single
+/// pass from the end.  Each pass only ever asks "does a word start (or end) here?", so overlapping
+/// words such as `oneight` yield `1` going forwards and `8` going backwards.
+#[derive(Debug, Clone)]
+pub struct DigitTokenizer {
+    forward: Trie,
+    backward: Trie,
+}
+
+impl DigitTokenizer {
+    /// A tokenizer that only recognises the numerals `0` to `9`.
+    pub fn numerals() -> DigitTokenizer {
+        DigitTokenizer::new(&[])
+    }
+
+    /// A tokenizer that recognises numerals and the English words `one` to `nine`.
+    pub fn english() -> DigitTokenizer {
+        DigitTokenizer::new(&ENGLISH_DIGITS)
+    }
+
+    /// A tokenizer that recognises numerals along with each of `words`.
+    pub fn new(words: &[(&str, u8)]) -> DigitTokenizer {
+        DigitTokenizer::build(words.iter().copied())
+    }
+
+    /// A tokenizer that recognises numerals along with every word in `vocabulary`.
+    pub fn with_vocabulary(vocabulary: &Vocabulary) -> DigitTokenizer {
+        DigitTokenizer::build(vocabulary.words())
+    }
+
+    fn build<'a>(words: impl Iterator<Item = (&'a str, u8)>) -> DigitTokenizer {
+        let mut forward = Trie::default();
+        let mut backward = Trie::default();
+        for (word, digit) in words {
+            forward.insert(word.bytes(), digit);
+            backward.insert(word.bytes().rev(), digit);
+        }
+        DigitTokenizer { forward, backward }
+    }
+
+    /// The first digit in `line`.
+    pub fn first_digit(&self, line: &str) -> Option<u8> {
+        self.first_token(line).map(|token| token.digit)
+    }
+
+    /// The last digit in `line`.
+    pub fn last_digit(&self, line: &str) -> Option<u8> {
+        self.last_token(line).map(|token| token.digit)
+    }
+
+    /// The first digit in `line` along with where it was found.
+    pub fn first_token<'a>(&self, line: &'a str) -> Option<DigitToken<'a>> {
+        (0..line.len()).find_map(|start| self.token_at(line, start))
+    }
+
+    /// The last digit in `line` along with where it was found.
+    pub fn last_token<'a>(&self, line: &'a str) -> Option<DigitToken<'a>> {
+        let bytes = line.as_bytes();
+        (1..=bytes.len()).rev().find_map(|end| {
+            let (digit, len) = match bytes[end - 1] {
+                b @ b'0'..=b'9' => (b - b'0', 1),
+                _ => self.backward.find(bytes[..end].iter().rev().copied())?,
+            };
+            Some(DigitToken { offset: end - len, text: &line[end - len..end], digit })
+        })
+    }
+
+    /// Every digit in `line`, numeral or word, in order of where it starts.  Overlapping words are
+    /// all included, so `twone` yields both `two` and `one`.
+    pub fn tokens<'a>(&'a self, line: &'a str) -> impl Iterator<Item = DigitToken<'a>> + 'a {
+        (0..line.len()).filter_map(move |start| self.token_at(line, start))
+    }
+
+    /// The digit starting at byte `start` of `line`, if any.
+    fn token_at<'a>(&self, line: &'a str, start: usize) -> Option<DigitToken<'a>> {
+        let bytes = line.as_bytes();
+        let (digit, len) = match bytes[start] {
+            b @ b'0'..=b'9' => (b - b'0', 1),
+            _ => self.forward.find(bytes[start..].iter().copied())?,
+        };
+        Some(DigitToken { offset: start, text: &line[start..start + len], digit })
+    }
+
+    /// The two-digit number formed by the first and last digits of `line`, if it has any digits.
+    pub fn calibration_value(&self, line: &str) -> Option<usize> {
+        let first = self.first_digit(line)?;
+        let last = self.last_digit(line)?;
+        Some(first as usize * 10 + last as usize)
+    }
+}
+
+/// A byte-wise trie mapping words onto the digit they spell.
+#[derive(Debug, Clone)]
+struct Trie {
+    nodes: Vec<TrieNode>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct TrieNode {
+    children: Vec<(u8, usize)>,
+    digit: Option<u8>,
+}
+
+impl Default for Trie {
+    fn default() -> Self {
+        Trie { nodes: vec![TrieNode::default()] }
+    }
+}
+
+impl Trie {
+    fn insert(&mut self, word: impl Iterator<Item = u8>, digit: u8) {
+        let mut node = 0;
+        for byte in word {
+            node = match self.child(node, byte) {
+                Some(child) => child,
+                None => {
+                    self.nodes.push(TrieNode::default());
+                    let child = self.nodes.len() - 1;
+                    self.nodes[node].children.push((byte, child));
+                    child
+                }
+            };
+        }
+        self.nodes[node].digit = Some(digit);
+    }
+
+    fn child(&self, node: usize, byte: u8) -> Option<usize> {
+        self.nodes[node]
+            .children
+            .iter()
+            .find(|&&(b, _)| b == byte)
+            .map(|&(_, child)| child)
+    }
+
+    /// The digit and length of the shortest word that `bytes` starts with.
+    fn find(&self, bytes: impl Iterator<Item = u8>) -> Option<(u8, usize)> {
+        let mut node = 0;
+        for (len, byte) in bytes.enumerate() {
+            node = self.child(node, byte)?;
+            if let Some(digit) = self.nodes[node].digit {
+                return Some((digit, len + 1));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PART_ONE_EXAMPLE: &str = "\
+1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet
+";
+
+    const PART_TWO_EXAMPLE: &str = "\
+two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen
+";
+
+    #[test]
+    fn part_one_example() {
+        assert_eq!(part_one(PART_ONE_EXAMPLE).unwrap(), 142);
+    }
+
+    #[test]
+    fn part_two_example() {
+        assert_eq!(part_two(PART_TWO_EXAMPLE).unwrap(), 281);
+    }
+
+    #[test]
+    fn overlapping_words_yield_first_and_last_digit() {
+        let tokenizer = DigitTokenizer::english();
+        assert_eq!(tokenizer.calibration_value("oneight"), Some(18));
+        assert_eq!(tokenizer.calibration_value("twone"), Some(21));
+        assert_eq!(tokenizer.calibration_value("sevenine"), Some(79));
+        assert_eq!(tokenizer.calibration_value("eightwo"), Some(82));
+        assert_eq!(tokenizer.calibration_value("xtwone3four"), Some(24));
+        assert_eq!(tokenizer.calibration_value("zoneight234"), Some(14));
+    }
+
+    #[test]
+    fn single_digit_is_both_first_and_last() {
+        let tokenizer = DigitTokenizer::english();
+        assert_eq!(tokenizer.calibration_value("treb7uchet"), Some(77));
+        assert_eq!(tokenizer.calibration_value("xxsixxx"), Some(66));
+    }
+
+    #[test]
+    fn numerals_only_ignores_words() {
+        let tokenizer = DigitTokenizer::numerals();
+        assert_eq!(tokenizer.calibration_value("one2three4five"), Some(24));
+        assert_eq!(tokenizer.calibration_value("oneight"), None);
+    }
+
+    #[test]
+    fn other_languages_and_custom_words() {
+        let german = DigitTokenizer::with_vocabulary(&Vocabulary::named("german").unwrap());
+        assert_eq!(german.calibration_value("xfünfzehnachtx"), Some(58));
+
+        let spanish = DigitTokenizer::with_vocabulary(&Vocabulary::named("es").unwrap());
+        assert_eq!(spanish.calibration_value("cerodosiete"), Some(27));
+
+        let with_zero = Vocabulary::named("english").unwrap().with_word("zero", 0);
+        assert_eq!(DigitTokenizer::with_vocabulary(&with_zero).calibration_value("zerone"), Some(1));
+    }
+
+    #[test]
+    fn vocabulary_file() {
+        let vocabulary = Vocabulary::parse("# French, plus zero\nlanguage = french\nzéro = 0\n").unwrap();
+        let tokenizer = DigitTokenizer::with_vocabulary(&vocabulary);
+        assert_eq!(tokenizer.calibration_value("zérotroisx"), Some(3));
+
+        assert!(matches!(
+            Vocabulary::parse("one = 1\ntwo = 22"),
+            Err(AocError::Parse { line: 2, column: 7, .. }),
+        ));
+        assert!(matches!(
+            Vocabulary::parse("language = klingon"),
+            Err(AocError::Parse { line: 1, column: 12, .. }),
+        ));
+    }
+
+    #[test]
+    fn lines_without_a_digit_are_rejected() {
+        let input = "1abc2\nnodigits";
+        assert_eq!(part_one(input).unwrap_err().to_string(), "line 2, column 1: no digit on line");
+        assert_eq!(part_two(input).unwrap_err().to_string(), "line 2, column 1: no digit on line");
+        assert_eq!(part_two("1abc2\nnone").unwrap(), 12 + 11);
+    }
+
+    #[test]
+    fn explain_reports_every_digit_and_lines_without_any() {
+        let tokenizer = DigitTokenizer::english();
+        let reports: Vec<LineReport> = explain("xtwone3\nabc", &tokenizer).collect();
+
+        assert_eq!(reports[0].line, 1);
+        assert_eq!(
+            reports[0].tokens.iter().map(|t| (t.text, t.offset)).collect::<Vec<_>>(),
+            vec![("two", 1), ("one", 3), ("3", 6)],
+        );
+        assert_eq!(reports[0].value(), Some(23));
+        assert_eq!(reports[0].to_string(), "1\t23\ttwo@1\t3@6\ttwo@1 one@3 3@6");
+
+        assert_eq!(reports[1].value(), None);
+        assert_eq!(reports[1].to_string(), "2\t-\t-\t-\tNO DIGITS");
+        assert_eq!(
+            reports[1].to_json(),
+            r#"{"line":2,"value":null,"first":null,"last":null,"tokens":[],"no_digits":true}"#,
+        );
+    }
+
+    #[test]
+    fn partial_words_are_not_digits() {
+        let tokenizer = DigitTokenizer::english();
+        assert_eq!(tokenizer.calibration_value("onthreeigh"), Some(33));
+        assert_eq!(tokenizer.calibration_value("abc"), None);
+    }
+}
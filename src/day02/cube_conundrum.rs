@@ -0,0 +1,463 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::str::FromStr;
+use crate::error::{AocError, Result};
+use crate::solution::{Answer, Solution};
+
+/// Day 2: Cube Conundrum
+pub struct CubeConundrum;
+
+impl Solution for CubeConundrum {
+    const DAY: u8 = 2;
+    const NAME: &'static str = "cube_conundrum";
+
+    type Input = Vec<Game>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Result<Answer> {
+        Ok(possible_game_id_sum(input, &BagLimits::default()).into())
+    }
+
+    fn part_2(input: &Self::Input) -> Result<Answer> {
+        Ok(minimum_power_sum(input).into())
+    }
+}
+
+/// The colors of cube in the puzzle as written.
+pub const STANDARD_COLORS: [&str; 3] = ["red", "green", "blue"];
+
+/// A number of cubes of each color: either a handful shown by the Elf or the contents of a bag.
+/// Any color can appear; a color that isn't listed has no cubes.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CubeSet {
+    counts: BTreeMap<String, usize>,
+}
+
+impl CubeSet {
+    pub fn new() -> CubeSet {
+        CubeSet::default()
+    }
+
+    /// The number of `color` cubes in the set.
+    pub fn count(&self, color: &str) -> usize {
+        self.counts.get(color).copied().unwrap_or(0)
+    }
+
+    /// Sets the number of `color` cubes in the set.
+    pub fn set(&mut self, color: &str, count: usize) {
+        self.counts.insert(color.to_string(), count);
+    }
+
+    /// Each color listed in the set along with its count, in alphabetical order.
+    pub fn colors(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.counts.iter().map(|(color, &count)| (color.as_str(), count))
+    }
+
+    /// The larger count of each color from `self` and `other`.
+    pub fn max(&self, other: &CubeSet) -> CubeSet {
+        let mut max = self.clone();
+        for (color, count) in other.colors() {
+            let entry = max.counts.entry(color.to_string()).or_default();
+            *entry = (*entry).max(count);
+        }
+        max
+    }
+
+    /// The *power* of the set: the counts of red, green, blue and every other color in the set
+    /// multiplied together.  A set missing any of red, green or blue has no power.
+    pub fn power(&self) -> usize {
+        let colors: BTreeSet<&str> = STANDARD_COLORS.into_iter().chain(self.counts.keys().map(String::as_str)).collect();
+        colors.into_iter().map(|color| self.count(color)).product()
+    }
+
+    /// The number of cubes of any color.
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Whether every cube in `self` could have come out of `bag`.
+    pub fn fits_within(&self, bag: &CubeSet) -> bool {
+        self.colors().all(|(color, count)| count <= bag.count(color))
+    }
+}
+
+impl<'a> FromIterator<(&'a str, usize)> for CubeSet {
+    fn from_iter<I: IntoIterator<Item = (&'a str, usize)>>(counts: I) -> CubeSet {
+        let mut cubes = CubeSet::new();
+        for (color, count) in counts {
+            cubes.set(color, cubes.count(color) + count);
+        }
+        cubes
+    }
+}
+
+/// Which colors of cube the input may mention.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Colors {
+    /// Any color at all.
+    #[default]
+    Any,
+    /// Only these colors; any other is a parse error.
+    Only(Vec<String>),
+}
+
+impl Colors {
+    /// Only red, green and blue.
+    pub fn standard() -> Colors {
+        Colors::Only(STANDARD_COLORS.iter().map(|color| color.to_string()).collect())
+    }
+
+    fn allows(&self, color: &str) -> bool {
+        match self {
+            Colors::Any => true,
+            Colors::Only(colors) => colors.iter().any(|allowed| allowed == color),
+        }
+    }
+}
+
+/// The cubes loaded into the bag, which limit what the Elf could have shown in any one round.  The
+/// default is the bag part one asks about: 12 red cubes, 13 green cubes and 14 blue cubes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BagLimits {
+    pub cubes: CubeSet,
+}
+
+impl BagLimits {
+    pub fn new(red: usize, green: usize, blue: usize) -> BagLimits {
+        BagLimits { cubes: [("red", red), ("green", green), ("blue", blue)].into_iter().collect() }
+    }
+
+    /// Whether `game` could have been played with this bag.
+    pub fn allows(&self, game: &Game) -> bool {
+        game.is_possible_with(&self.cubes)
+    }
+}
+
+impl Default for BagLimits {
+    fn default() -> BagLimits {
+        BagLimits::new(12, 13, 14)
+    }
+}
+
+/// Parses a bag written the same way as a round, e.g. `12 red, 13 green, 14 blue`.  Any color may
+/// be listed; colors left out have no cubes in the bag.
+impl FromStr for BagLimits {
+    type Err = AocError;
+
+    fn from_str(query: &str) -> Result<BagLimits> {
+        Ok(BagLimits { cubes: parse_cube_set(1, query, query, &Colors::Any)? })
+    }
+}
+
+/// One game: its ID and the handful of cubes shown in each round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub id: usize,
+    pub rounds: Vec<CubeSet>,
+}
+
+impl Game {
+    /// The fewest cubes of each color the bag could have held for this game to be possible.
+    pub fn minimum_bag(&self) -> CubeSet {
+        self.rounds.iter().fold(CubeSet::new(), |bag, round| bag.max(round))
+    }
+
+    /// Whether every round of this game could have been drawn from `bag`.
+    pub fn is_possible_with(&self, bag: &CubeSet) -> bool {
+        self.rounds.iter().all(|round| round.fits_within(bag))
+    }
+}
+
+/// Parses one `Game <id>: <round>; <round>; ...` line per game.  Blank lines are skipped, and
+/// games may be listed in any order, but each ID may only be used once.  Cubes may be any color.
+pub fn parse(input: &str) -> Result<Vec<Game>> {
+    parse_with_colors(input, &Colors::Any)
+}
+
+/// Parses games like [`parse`], rejecting any cube whose color `colors` doesn't allow.
+pub fn parse_with_colors(input: &str, colors: &Colors) -> Result<Vec<Game>> {
+    let mut games: Vec<Game> = Vec::new();
+    let mut listed_on = HashMap::new();
+
+    for (line_num, line) in input.lines().enumerate() {
+        let line_num = line_num + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let game = parse_game(line_num, line, colors)?;
+        if let Some(first) = listed_on.insert(game.id, line_num) {
+            return Err(AocError::parse(
+                line_num,
+                1,
+                format!("game {} is already listed on line {}", game.id, first),
+            ));
+        }
+        games.push(game);
+    }
+
+    Ok(games)
+}
+
+/// Parses a single game from `line`, which is line `line_num` of the input.
+fn parse_game(line_num: usize, line: &str, colors: &Colors) -> Result<Game> {
+    let (id, rounds) = line
+        .strip_prefix("Game ")
+        .and_then(|rest| rest.split_once(':'))
+        .ok_or_else(|| AocError::parse(line_num, 1, format!("expected line to start with `Game <id>:`, found `{}`", line)))?;
+    let id = id
+        .trim()
+        .parse()
+        .map_err(|err| AocError::parse_at(line_num, line, id, format!("invalid game id `{}`: {}", id, err)))?;
+    let rounds = rounds
+        .split(';')
+        .map(|round| parse_cube_set(line_num, line, round, colors))
+        .collect::<Result<_>>()?;
+
+    Ok(Game { id, rounds })
+}
+
+/// Parses one round, a comma-separated list of `<count> <color>` slices of `line`.
+fn parse_cube_set(line_num: usize, line: &str, round: &str, colors: &Colors) -> Result<CubeSet> {
+    let mut cubes = CubeSet::new();
+    for cubes_of_color in round.split(',') {
+        let cubes_of_color = cubes_of_color.trim();
+        let (count, color) = cubes_of_color.split_once(' ').ok_or_else(|| {
+            AocError::parse_at(line_num, line, cubes_of_color, format!("expected `<count> <color>`, found `{}`", cubes_of_color))
+        })?;
+        let count: usize = count.parse().map_err(|err| {
+            AocError::parse_at(line_num, line, count, format!("invalid cube count `{}`: {}", count, err))
+        })?;
+        let color = color.trim();
+        if color.is_empty() || color.contains(char::is_whitespace) {
+            return Err(AocError::parse_at(line_num, line, cubes_of_color, format!("expected `<count> <color>`, found `{}`", cubes_of_color)));
+        }
+        if !colors.allows(color) {
+            return Err(AocError::parse_at(line_num, line, color, format!("unexpected color `{}`", color)));
+        }
+        cubes.set(color, cubes.count(color) + count);
+    }
+    Ok(cubes)
+}
+
+/// The sum of the IDs of the games that could have been played with `bag`.
+pub fn possible_game_id_sum(games: &[Game], bag: &BagLimits) -> usize {
+    games.iter().filter(|game| bag.allows(game)).map(|game| game.id).sum()
+}
+
+/// The sum of the [powers](CubeSet::power) of the smallest bag each game could have been played
+/// with.
+pub fn minimum_power_sum(games: &[Game]) -> usize {
+    games.iter().map(|game| game.minimum_bag().power()).sum()
+}
+
+/// You're launching high into the atmosphere!  The apex of your trajectory just barely reaches the
+/// surface of a large island floating in the sky. You gently land in a fluffy pile of leaves. It's
+/// quite cold, but you don't see much snow. A Elf runs over to greet you.
+///
+/// The Elf explains that you've arrived at *Snow Island* and apologizes for the lack of snow. He'll
+/// be happy to explain the situation, but it's a bit of a walk, so you have some time. They don't
+/// get many visitors up here; would you like to play a game in the meantime?
+///
+/// As you walk, the Elf shows you a small bag and some cubes which are either red, green, or blue.
+/// Each time you play this game, he will hide a secret number of cubes of each color in the bag,
+/// and your goal is to figure out information about the number of cubes.
+///
+/// To get information, once a bag has been loaded with cubes, the Elf will reach into the bag, grab
+/// a handful of random cubes, show them to you, and then put them back in the bag. He'll do this a
+/// few times per game.
+///
+/// You play several games and record the information from each game (your puzzle input). Each game
+/// is listed with its ID number (like the `11` in `Game 1: ...`) followed by a semicolon-separated
+/// list of subsets of cubes that were revealed from the bag (like `3 red, 5 green, 4 blue`).
+///
+/// # Example
+///
+/// ```text
+/// Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+/// Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+/// Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+/// Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+/// Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+/// ```
+///
+/// In game 1, three sets of cubes are revealed from the bag (and then put back again). The first
+/// set is 3 blue cubes and 4 red cubes; the second set is 1 red cube, 2 green cubes, and 6 blue
+/// cubes; the third set is only 2 green cubes.
+///
+/// The Elf would first like to know which games would have been possible if the bag contained
+/// *only 12 red cubes, 13 green cubes, and 14 blue cubes*?
+///
+/// In the example above, games 1, 2, and 5 would have been *possible* if the bag had been loaded
+/// with that configuration.  However, game 3 would have been *impossible* because at one point the
+/// Elf showed you 20 red cubes at once; similarly, game 4 would also have been *impossible* because
+/// the Elf showed you 15 blue cubes at once. If you add up the IDs of the games that would have
+/// been possible, you get *8*.
+///
+/// Determine which games would have been possible if the bag had been loaded with only 12 red cubes,
+/// 13 green cubes, and 14 blue cubes. *What is the sum of the IDs of those games?*
+pub fn part_1(input: &str) -> Result<usize> {
+    Ok(possible_game_id_sum(&parse(input)?, &BagLimits::default()))
+}
+
+/// The Elf says they've stopped producing snow because they aren't getting any *water*! He isn't
+/// sure why the water stopped; however, he can show you how to get the water source to check it out
+/// for yourself. It's just up ahead!
+///
+/// As you continue your walk, the Elf poses a second question: in each game you played, what is the
+/// *fewest number of cubes of each color* that could have been in the bag to make the game possible?
+///
+/// Again consider the example games from earlier:
+///
+/// # Example
+///
+/// ```text
+/// Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+/// Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+/// Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+/// Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+/// Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+/// ```
+///
+/// * In game 1, the game could have been played with as few as 4 red, 2 green, and 6 blue cubes. If
+///   any color had been one fewer cube, the game would have been impossible.
+/// * Game 2 could have been played with a minimum of 1 red, 3 green, and 4 blue cubes.
+/// * Game 3 must have been played with at least 20 red, 13 green, and 6 blue cubes.
+/// * Game 4 required at least 14 red, 3 green, and 15 blue cubes.
+/// * Game 5 needed no fewer than 6 red, 3 green, and 2 blue cubes in the bag.
+///
+/// The *power* of a set of cubes is equal to the numbers of red, green, and blue cubes multiplied
+/// together. The power of the minimum set of cubes in game 1 is `48`. In games 2-5 it was `12`,
+/// `1560`, `630`, and `36`, respectively.  Adding up these five powers produces the sum `2286`.
+///
+/// For each game, find the minimum set of cubes that must have been present. *What is the sum of
+/// the power of these sets?*
+pub fn part_2(input: &str) -> Result<usize> {
+    Ok(minimum_power_sum(&parse(input)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+";
+
+    #[test]
+    fn part_1_example() {
+        assert_eq!(part_1(EXAMPLE).unwrap(), 8);
+    }
+
+    #[test]
+    fn part_2_example() {
+        assert_eq!(part_2(EXAMPLE).unwrap(), 2286);
+    }
+
+    #[test]
+    fn rounds_are_parsed_separately() {
+        let game = parse_game(1, "Game 7: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green", &Colors::Any).unwrap();
+        assert_eq!(game.id, 7);
+        assert_eq!(
+            game.rounds,
+            vec![
+                CubeSet::from_iter([("red", 4), ("blue", 3)]),
+                CubeSet::from_iter([("red", 1), ("green", 2), ("blue", 6)]),
+                CubeSet::from_iter([("green", 2)]),
+            ],
+        );
+        assert_eq!(game.minimum_bag(), CubeSet::from_iter([("red", 4), ("green", 2), ("blue", 6)]));
+        assert_eq!(game.minimum_bag().power(), 48);
+    }
+
+    #[test]
+    fn each_round_is_checked_against_the_bag() {
+        // 13 red in total, but never more than 12 at once.
+        let game = parse_game(1, "Game 1: 12 red; 1 red", &Colors::Any).unwrap();
+        assert!(BagLimits::default().allows(&game));
+        assert!(!BagLimits::default().allows(&parse_game(1, "Game 1: 1 red; 13 red", &Colors::Any).unwrap()));
+    }
+
+    #[test]
+    fn malformed_rounds_are_reported_where_they_occur() {
+        let err = parse_game(3, "Game 1: 3 blue, x red", &Colors::Any).unwrap_err();
+        assert!(matches!(err, AocError::Parse { line: 3, column: 17, .. }), "{}", err);
+    }
+
+    #[test]
+    fn game_ids_come_from_the_input_not_the_line_number() {
+        let input = "\
+Game 5: 6 red, 1 blue, 3 green
+
+Game 2: 1 blue, 2 green
+Game 9: 20 red
+";
+        let games = parse(input).unwrap();
+        assert_eq!(games.iter().map(|game| game.id).collect::<Vec<_>>(), vec![5, 2, 9]);
+        assert_eq!(part_1(input).unwrap(), 5 + 2);
+    }
+
+    #[test]
+    fn lines_without_a_game_id_are_rejected() {
+        let err = parse("Game 1: 1 red\n3 blue, 4 red").unwrap_err();
+        assert!(matches!(err, AocError::Parse { line: 2, column: 1, .. }), "{}", err);
+        assert!(parse("Game x: 1 red").is_err());
+    }
+
+    #[test]
+    fn duplicate_game_ids_are_rejected() {
+        let err = parse("Game 1: 1 red\nGame 1: 2 red").unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 1: game 1 is already listed on line 1");
+    }
+
+    #[test]
+    fn bag_limits_can_be_parsed_from_a_query() {
+        let bag: BagLimits = "12 red, 13 green, 14 blue".parse().unwrap();
+        assert_eq!(bag, BagLimits::default());
+
+        let games = parse("Game 1: 3 red, 1 blue\nGame 2: 2 red\nGame 3: 1 green").unwrap();
+        assert_eq!(possible_game_id_sum(&games, &"3 red".parse().unwrap()), 2);
+        assert_eq!(possible_game_id_sum(&games, &BagLimits::new(3, 1, 1)), 1 + 2 + 3);
+        assert!("12 red, lots green".parse::<BagLimits>().is_err());
+    }
+
+    #[test]
+    fn missing_colors_have_no_power() {
+        assert_eq!(part_2("Game 1: 1 red, 2 blue").unwrap(), 0);
+        assert_eq!(part_2("Game 1: 1 red, 2 blue\nGame 2: 1 red, 2 green; 3 blue").unwrap(), 6);
+        assert_eq!(CubeSet::from_iter([("red", 1), ("blue", 2)]).power(), 0);
+    }
+
+    #[test]
+    fn any_color_can_be_used() {
+        let input = "\
+Game 1: 2 yellow, 1 red, 1 green, 1 blue; 3 yellow, 2 red
+Game 2: 1 red, 1 green, 1 blue, 1 yellow
+Game 3: 4 red, 5 green, 6 blue";
+        let games = parse(input).unwrap();
+        assert_eq!(
+            games[0].minimum_bag(),
+            CubeSet::from_iter([("yellow", 3), ("red", 2), ("green", 1), ("blue", 1)]),
+        );
+        // Yellow in games 1 and 2 doesn't take the power away from game 3, which shows none.
+        assert_eq!(games[2].minimum_bag().power(), 4 * 5 * 6);
+        assert_eq!(minimum_power_sum(&games), 3 * 2 + 1 + 4 * 5 * 6);
+
+        // The default bag has no yellow cubes, so games 1 and 2 can't have been played with it.
+        assert_eq!(possible_game_id_sum(&games, &BagLimits::default()), 3);
+        assert_eq!(possible_game_id_sum(&games, &"3 yellow, 2 red, 1 green, 1 blue".parse().unwrap()), 1 + 2);
+    }
+
+    #[test]
+    fn unexpected_colors_can_be_rejected() {
+        let input = "Game 1: 1 red, 2 yellow";
+        assert!(parse_with_colors(input, &Colors::Any).is_ok());
+        let err = parse_with_colors(input, &Colors::standard()).unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 18: unexpected color `yellow`");
+    }
+}
@@ -0,0 +1,5 @@
+//! Day 2: Cube Conundrum
+
+pub mod cube_conundrum;
+
+pub use cube_conundrum::CubeConundrum;
@@ -0,0 +1,358 @@
+use crate::error::{AocError, Result};
+use crate::grid::{Grid, Pos};
+use crate::solution::{Answer, Solution};
+
+/// Day 3: Gear Ratios
+pub struct GearRatios;
+
+impl Solution for GearRatios {
+    const DAY: u8 = 3;
+    const NAME: &'static str = "gear_ratios";
+
+    type Input = Schematic;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Schematic::parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Result<Answer> {
+        Ok(part_number_sum(input).into())
+    }
+
+    fn part_2(input: &Self::Input) -> Result<Answer> {
+        Ok(gear_ratio_sum(input).into())
+    }
+}
+
+/// A number in the schematic, spanning columns `col_start..col_end` of `row`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PartNumber {
+    pub value: usize,
+    pub row: usize,
+    pub col_start: usize,
+    pub col_end: usize,
+}
+
+/// Anything in the schematic other than a digit or a period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Symbol {
+    pub ch: char,
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Symbol {
+    pub fn pos(&self) -> Pos {
+        Pos::new(self.row, self.col)
+    }
+}
+
+/// Every number and symbol in an engine schematic, along with where they sit so either can be
+/// found from the other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schematic {
+    pub numbers: Vec<PartNumber>,
+    pub symbols: Vec<Symbol>,
+    /// For each cell, the index into `numbers` of the number covering it.
+    number_at: Grid<Option<usize>>,
+    /// For each cell, the index into `symbols` of the symbol on it.
+    symbol_at: Grid<Option<usize>>,
+}
+
+impl Schematic {
+    /// Scans a schematic for its numbers and symbols.  A number ends at the first non-digit after
+    /// it or at the end of its row, whichever comes first, so numbers never run onto the next row.
+    pub fn parse(input: &str) -> Result<Schematic> {
+        let grid: Grid<u8> = input.parse()?;
+        let mut numbers = Vec::new();
+        let mut symbols = Vec::new();
+        let mut number_at = grid.map(|_| None);
+        let mut symbol_at = grid.map(|_| None);
+
+        for (row, line) in grid.rows().enumerate() {
+            let mut col = 0;
+            while col < line.len() {
+                if line[col].is_ascii_digit() {
+                    let col_start = col;
+                    while col < line.len() && line[col].is_ascii_digit() {
+                        number_at[Pos::new(row, col)] = Some(numbers.len());
+                        col += 1;
+                    }
+                    let digits = std::str::from_utf8(&line[col_start..col]).expect("digits are ASCII");
+                    let value = digits.parse().map_err(|err| {
+                        AocError::parse(row + 1, col_start + 1, format!("invalid part number `{}`: {}", digits, err))
+                    })?;
+                    numbers.push(PartNumber { value, row, col_start, col_end: col });
+                } else {
+                    if line[col] != b'.' {
+                        symbol_at[Pos::new(row, col)] = Some(symbols.len());
+                        symbols.push(Symbol { ch: line[col] as char, row, col });
+                    }
+                    col += 1;
+                }
+            }
+        }
+
+        Ok(Schematic { numbers, symbols, number_at, symbol_at })
+    }
+
+    /// The symbols touching `number`, even diagonally.
+    pub fn symbols_adjacent_to<'a>(&'a self, number: &PartNumber) -> impl Iterator<Item = &'a Symbol> + 'a {
+        let rows = number.row.saturating_sub(1)..=number.row + 1;
+        let cols = number.col_start.saturating_sub(1)..=number.col_end;
+        rows.flat_map(move |row| cols.clone().map(move |col| Pos::new(row, col)))
+            .filter_map(|pos| self.symbol_at.get(pos).copied().flatten())
+            .map(|index| &self.symbols[index])
+    }
+
+    /// The numbers touching `symbol`, even diagonally, each listed once.
+    pub fn numbers_adjacent_to(&self, symbol: &Symbol) -> Vec<&PartNumber> {
+        let mut indices: Vec<usize> = self
+            .number_at
+            .neighbors8(symbol.pos())
+            .filter_map(|pos| self.number_at[pos])
+            .collect();
+        indices.sort_unstable();
+        indices.dedup();
+        indices.into_iter().map(|index| &self.numbers[index]).collect()
+    }
+
+    /// Whether `number` is a real part number, i.e. touches a symbol.
+    pub fn is_part_number(&self, number: &PartNumber) -> bool {
+        self.symbols_adjacent_to(number).next().is_some()
+    }
+}
+
+/// You and the Elf eventually reach a gondola lift station; he says the gondola lift will take you
+/// up to the *water source*, but this is as far as he can bring you. You go inside.
+///
+/// It doesn't take long to find the gondolas, but there seems to be a problem: they're not moving.
+///
+/// "Aaah!"
+///
+/// You turn around to see a slightly-greasy Elf with a wrench and a look of surprise. "Sorry, row_num
+/// wasn't expecting anyone! The gondola lift isn't working right now; it'll still be a while before
+/// row_num can fix it." You offer to help.
+///
+/// The engineer explains that an engine part seems to be missing from the engine, but nobody can
+/// figure out which one. If you can *add up all the part numbers* in the engine schematic, it should
+/// be easy to work out which part is missing.
+///
+/// The engine schematic (your puzzle input) consists of a visual representation of the engine.
+/// There are lots of numbers and symbols you don't really understand, but apparently *any number
+/// adjacent to a symbol*, even diagonally, is a "part number" and should be included in your sum.
+/// (Periods (`.`) do not count as a symbol.)
+///
+/// Here is an example engine schematic:
+/// ```text
+/// 467..114..
+/// ...*......
+/// ..35..633.
+/// ......#...
+/// 617*......
+/// .....+.58.
+/// ..592.....
+/// ......755.
+/// ...$.*....
+/// .664.598..
+/// ```
+///
+/// In this schematic, two numbers are *not* part numbers because they are not adjacent to a symbol:
+/// `114` (top right) and `58` (middle right). Every other number is adjacent to a symbol and so is a
+/// part number; their sum is `4361`.
+///
+/// Of course, the actual engine schematic is much larger. *What is the sum of all of the part
+/// numbers in the engine schematic?*
+pub fn part_1(input: &str) -> Result<usize> {
+    Ok(part_number_sum(&Schematic::parse(input)?))
+}
+
+/// The sum of every number in `schematic` that's adjacent to a symbol.
+pub fn part_number_sum(schematic: &Schematic) -> usize {
+    // 537732
+    schematic
+        .numbers
+        .iter()
+        .filter(|number| schematic.is_part_number(number))
+        .map(|number| number.value)
+        .sum()
+}
+
+/// The engineer finds the missing part and installs it in the engine!  As the engine springs to
+/// life, you jump in the closest gondola, finally ready to ascend to the water source.
+///
+/// You don't seem to be going very fast, though.  Maybe something is still wrong?  Fortunately, the
+/// gondola has a phone labeled "help", so you pick it up and the engineer answers.
+///
+/// Before you can explain the situation, she suggests that you look out the window.  There stands
+/// the engineer, holding a phone in one hand and waving with the other.  You're going so slowly that
+/// you haven't even left the station.  You exit the gondola.
+///
+/// The missing part wasn't the only issue - one of the gears in the engine is wrong.  A *gear* is
+/// any `*` symbol that is adjacent to *exactly two part numbers*.  Its *gear ratio* is the result of
+/// multiplying those two numbers together.
+///
+/// This time, you need to find the gear ratio of every gear and add them all up so that the
+/// engineer can figure out which gear needs to be replaced.
+///
+/// Consider the same engine schematic again:
+/// ```text
+/// 467..114..
+/// ...*......
+/// ..35..633.
+/// ......#...
+/// 617*......
+/// .....+.58.
+/// ..592.....
+/// ......755.
+/// ...$.*....
+/// .664.598..
+/// ```
+///
+/// In this schematic, there are *two* gears.  The first is in the top left; it has part numbers `467`
+/// and `35`, so its gear ratio is `16345`.  The second gear is in the lower right; its gear ratio is
+/// `451490`.  (The `*` adjacent to `617` is *not* a gear because it is only adjacent to one part
+/// number.)  Adding up all of the gear ratios produces `467835`.
+///
+/// *What is the sum of all of the gear ratios in your engine schematic?*
+pub fn part_2(input: &str) -> Result<usize> {
+    Ok(gear_ratio_sum(&Schematic::parse(input)?))
+}
+
+/// The sum of the gear ratios of every `*` in `schematic` that's adjacent to exactly two numbers.
+pub fn gear_ratio_sum(schematic: &Schematic) -> usize {
+    schematic
+        .symbols
+        .iter()
+        .filter(|symbol| symbol.ch == '*')
+        .map(|symbol| schematic.numbers_adjacent_to(symbol))
+        .filter(|numbers| numbers.len() == 2)
+        .map(|numbers| numbers[0].value * numbers[1].value)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+";
+
+    #[test]
+    fn part_1_example() {
+        assert_eq!(part_1(EXAMPLE).unwrap(), 4361);
+    }
+
+    #[test]
+    fn part_2_example() {
+        assert_eq!(part_2(EXAMPLE).unwrap(), 467835);
+    }
+
+    fn values(schematic: &Schematic) -> Vec<usize> {
+        schematic.numbers.iter().map(|number| number.value).collect()
+    }
+
+    #[test]
+    fn number_at_end_of_line_is_counted() {
+        let input = "...*\n..12\n....";
+        assert_eq!(part_1(input).unwrap(), 12);
+    }
+
+    #[test]
+    fn number_at_end_of_input_is_counted() {
+        assert_eq!(part_1("...#\n..34").unwrap(), 34);
+        assert_eq!(part_1("...#\n..34\n").unwrap(), 34);
+    }
+
+    #[test]
+    fn numbers_touching_every_edge_are_counted() {
+        let input = "\
+12.34
+*...$
+5..67
+..%..
+8.9.1";
+        // Every number touches an edge; `8` and `1` in the bottom corners touch no symbol.
+        let schematic = Schematic::parse(input).unwrap();
+        assert_eq!(values(&schematic), vec![12, 34, 5, 67, 8, 9, 1]);
+        assert_eq!(part_1(input).unwrap(), 12 + 34 + 5 + 67 + 9);
+    }
+
+    #[test]
+    fn numbers_do_not_run_onto_the_next_row() {
+        let schematic = Schematic::parse("..12\n3*..").unwrap();
+        assert_eq!(values(&schematic), vec![12, 3]);
+        assert_eq!(part_1("..12\n3*..").unwrap(), 15);
+    }
+
+    #[test]
+    fn number_spans_cover_the_whole_number() {
+        let schematic = Schematic::parse(".123\n....").unwrap();
+        assert_eq!(
+            schematic.numbers,
+            vec![PartNumber { value: 123, row: 0, col_start: 1, col_end: 4 }],
+        );
+    }
+
+    #[test]
+    fn crlf_line_endings_are_accepted() {
+        assert_eq!(part_1("..*\r\n.12\r\n").unwrap(), 12);
+    }
+
+    #[test]
+    fn gear_at_end_of_line_is_counted() {
+        assert_eq!(part_2("..12\n...*\n..34").unwrap(), 12 * 34);
+        assert_eq!(part_2("12*\n..3").unwrap(), 12 * 3);
+    }
+
+    #[test]
+    fn stars_touching_one_or_three_numbers_are_not_gears() {
+        assert_eq!(part_2("12*..\n.....").unwrap(), 0);
+        assert_eq!(part_2("1.2\n.*.\n3..").unwrap(), 0);
+    }
+
+    #[test]
+    fn number_touching_a_star_twice_counts_once() {
+        // Both digits of `12` touch the `*`, which is a gear with `3` and not with `12` twice.
+        assert_eq!(part_2("12.\n.*3").unwrap(), 12 * 3);
+    }
+
+    #[test]
+    fn number_touched_twice_by_one_symbol_is_listed_once() {
+        // The `*` touches both the `1` and the `3` of `123`.
+        let schematic = Schematic::parse("123\n.*.\n..4").unwrap();
+        let star = &schematic.symbols[0];
+        let values: Vec<usize> = schematic.numbers_adjacent_to(star).iter().map(|number| number.value).collect();
+        assert_eq!(values, vec![123, 4]);
+    }
+
+    #[test]
+    fn symbols_are_found_at_every_corner_of_a_number() {
+        let schematic = Schematic::parse("a...b\n.123.\nc...d").unwrap();
+        let number = &schematic.numbers[0];
+        let found: Vec<char> = schematic.symbols_adjacent_to(number).map(|symbol| symbol.ch).collect();
+        assert_eq!(found, vec!['a', 'b', 'c', 'd']);
+        for symbol in &schematic.symbols {
+            assert_eq!(schematic.numbers_adjacent_to(symbol), vec![number]);
+        }
+    }
+
+    #[test]
+    fn symbols_beyond_the_corners_are_not_adjacent() {
+        let schematic = Schematic::parse("#.....\n..12..\n.....#").unwrap();
+        let number = &schematic.numbers[0];
+        assert_eq!(schematic.symbols_adjacent_to(number).count(), 0);
+        assert!(!schematic.is_part_number(number));
+        assert!(schematic.symbols.iter().all(|symbol| schematic.numbers_adjacent_to(symbol).is_empty()));
+    }
+}
@@ -0,0 +1,5 @@
+//! Day 3: Gear Ratios
+
+pub mod gear_ratios;
+
+pub use gear_ratios::GearRatios;
@@ -0,0 +1,5 @@
+//! Day 4: Scratchcards
+
+pub mod scratchcards;
+
+pub use scratchcards::Scratchcards;
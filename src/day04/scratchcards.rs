@@ -0,0 +1,375 @@
+use std::collections::{HashMap, HashSet};
+use crate::error::{AocError, Result};
+use crate::solution::{Answer, Solution};
+
+/// Day 4: Scratchcards
+pub struct Scratchcards;
+
+impl Solution for Scratchcards {
+    const DAY: u8 = 4;
+    const NAME: &'static str = "scratchcards";
+
+    type Input = Vec<Card>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Result<Answer> {
+        total_points(input).map(Answer::from)
+    }
+
+    fn part_2(input: &Self::Input) -> Result<Answer> {
+        Ok(total_scratchcards(input).into())
+    }
+}
+
+/// One scratchcard: its number, the line it was listed on, its winning numbers and the numbers you
+/// have.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Card {
+    pub id: usize,
+    pub line: usize,
+    pub winning: HashSet<u32>,
+    pub have: Vec<u32>,
+}
+
+impl Card {
+    /// How many of the numbers you have are winning numbers.
+    pub fn matches(&self) -> usize {
+        self.have.iter().filter(|num| self.winning.contains(num)).count()
+    }
+
+    /// One point for the first match, doubled for each match after that, or `None` if that's too
+    /// many points to count.
+    pub fn points(&self) -> Option<usize> {
+        match self.matches() {
+            0 => Some(0),
+            n => 1usize.checked_shl(n as u32 - 1),
+        }
+    }
+}
+
+/// What to do about a card that wins copies of cards that aren't in the table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Overflow {
+    /// Win only the cards that are there, as the puzzle promises never to go past the end.
+    #[default]
+    Clamp,
+    /// Treat it as a mistake in the input.
+    Reject,
+}
+
+/// Parses one `Card <id>: <winning numbers> | <numbers you have>` line per card.  Blank lines are
+/// skipped, and each card number may only be used once.
+pub fn parse(input: &str) -> Result<Vec<Card>> {
+    parse_with_overflow(input, Overflow::Clamp)
+}
+
+/// Parses cards like [`parse`].  With [`Overflow::Reject`], a card that would win a copy of a card
+/// missing from the table is reported as an error on that card's line.
+pub fn parse_with_overflow(input: &str, overflow: Overflow) -> Result<Vec<Card>> {
+    let mut cards = Vec::new();
+    let mut listed_on = HashMap::new();
+
+    for (line_num, line) in input.lines().enumerate() {
+        let line_num = line_num + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let card = parse_card(line_num, line)?;
+        if let Some(first) = listed_on.insert(card.id, line_num) {
+            return Err(AocError::parse(
+                line_num,
+                1,
+                format!("card {} is already listed on line {}", card.id, first),
+            ));
+        }
+        cards.push(card);
+    }
+
+    if overflow == Overflow::Reject {
+        for card in &cards {
+            let won = card.id + 1..=card.id + card.matches();
+            if let Some(missing) = won.clone().find(|id| !listed_on.contains_key(id)) {
+                return Err(AocError::parse(
+                    card.line,
+                    1,
+                    format!(
+                        "card {} wins cards {} to {}, but there is no card {}",
+                        card.id,
+                        won.start(),
+                        won.end(),
+                        missing,
+                    ),
+                ));
+            }
+        }
+    }
+
+    Ok(cards)
+}
+
+/// Parses a single card from `line`, which is line `line_num` of the input.
+fn parse_card(line_num: usize, line: &str) -> Result<Card> {
+    let (id, numbers) = line
+        .strip_prefix("Card")
+        .and_then(|rest| rest.split_once(':'))
+        .ok_or_else(|| AocError::parse(line_num, 1, format!("expected line to start with `Card <id>:`, found `{}`", line)))?;
+    let id = id.trim();
+    let id = id
+        .parse()
+        .map_err(|err| AocError::parse_at(line_num, line, id, format!("invalid card number `{}`: {}", id, err)))?;
+    let (winning, have) = numbers
+        .split_once('|')
+        .ok_or_else(|| AocError::parse_at(line_num, line, numbers, "expected `|` between the two lists of numbers"))?;
+
+    let parse_nums = |nums: &str| -> Result<Vec<u32>> {
+        nums.split_ascii_whitespace()
+            .map(|n| {
+                n.parse::<u32>()
+                    .map_err(|err| AocError::parse_at(line_num, line, n, format!("invalid number `{}`: {}", n, err)))
+            })
+            .collect()
+    };
+
+    Ok(Card { id, line: line_num, winning: parse_nums(winning)?.into_iter().collect(), have: parse_nums(have)? })
+}
+
+/// The gondola takes you up.  Strangely, though, the ground doesn't seem to be coming with you;
+/// you're not climbing a mountain.  As the circle of Snow Island recedes below you, an entire new
+/// landmass suddenly appears above you!  The gondola carries you to the surface of the new island
+/// and lurches into the station.
+///
+/// As you exit the gondola, the first thing you notice is that the air here is much *warmer* than
+/// it was on Snow Island.  It's also quite *humid*.  Is this where the water source is?
+///
+/// The next thing you notice is an Elf sitting on the floor across the station in what seems to be
+/// a pile of colorful square cards.
+///
+/// "Oh!  Hello!" The Elf excitedly runs over to you.  "How may I be of service?" You ask about
+/// water sources.
+///
+/// "I'm not sure; I just operate the gondola lift.  That does sound like something we'd have,
+/// though - this is *Island Island*, after all!  I bet the *gardener* would know.  He's on a
+/// different island, though - er, the small kind surrounded by water, not the floating kind.  We
+/// really need to come up with a better naming scheme.  Tell you what: if you can help me with
+/// something quick, I'll let you *borrow my boat* and you can go visit the gardener.  I got all
+/// these scratchcards as a gift, but I can't figure out what I've won."
+///
+/// The Elf leads you over to the pile of colorful cards.  There, you discover dozens of scratchcards,
+/// all with their opaque covering already scratched off.  Picking one up, it looks like each card
+/// has two lists of numbers separated by a vertical bar ```|```: a list of *winning numbers* and
+/// then a list of *numbers you have*.  You organize the information into a table (your puzzle
+/// input).
+///
+/// As far as the Elf has been able to figure out, you have to figure out which of the *numbers you
+/// have* appear in the list of winning numbers.  The first match makes the card worth *one point*
+/// and each match after the first doubles the point value of that card.
+///
+/// # Example
+/// ```text
+/// Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+/// Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+/// Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+/// Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+/// Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+/// Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+/// ```
+///
+/// In the above example, card `1` has five winning numbers (`41`, `48`, `83`, `86`, and `17`) and
+/// eight numbers you have (`83`, `86`, `6`, `31`, `17`, `9`, `48`, and `53`).  Of the numbers you
+/// have, four of them (`48`, `83`, `17`, and `86`) are winning numbers!  That means that card `1`
+/// is worth `8` points (1 for the first match, then doubled three times for each of the three
+/// matches after the first).
+///
+/// * Card `2` has two winning numbers (`32` and `61`), so it is worth `2` points.
+/// * Card `3` has two winning numbers (`1` and `21`), so it is worth `2` points.
+/// * Card `4` has one winning number (`84`), so it is worth `1` point.
+/// * Card `5` has no winning numbers, so it is worth no points.
+/// * Card `6` has no winning numbers, so it is worth no points.
+///
+/// So, in this example, the Elf's pile of scratchcards is worth `13` points.
+///
+/// Take a seat in the large pile of colorful cards.  *How many points are they worth in total?*
+pub fn part_1(input: &str) -> Result<usize> {
+    total_points(&parse(input)?)
+}
+
+/// What every card in the pile is worth, added together.  A card with so many matches that its
+/// points can't be counted is reported as an error on its line.
+pub fn total_points(cards: &[Card]) -> Result<usize> {
+    cards.iter().try_fold(0usize, |total, card| {
+        card.points().and_then(|points| total.checked_add(points)).ok_or_else(|| {
+            AocError::parse(card.line, 1, format!("card {} has {} matches, too many points to count", card.id, card.matches()))
+        })
+    })
+}
+
+/// Just as you're about to report your findings to the Elf, one of you realizes that the rules have actually been
+/// printed on the back of every card this whole time.
+///
+/// There's no such thing as "points".  Instead, scratchcards only cause you to **win more scratchcards** equal to the
+/// number of winning numbers your have.
+///
+/// Specifically, you win **copies** of the scratchcards below the winning card equal to the number of matches.  So, if
+/// card 10 were to have 5 matching numbers, you would win one copy each of cards 11, 12, 13, 14, and 15.
+///
+/// Copies of scratchcards are scored like normal scratchcards and have the **same card number** as the card they copied.
+/// So, if you win a copy of card 10 and it has 5 matching numbers, it would then win a copy of the same cards that the
+/// original card 10 won: cards 11, 12, 13, 14, and 15.  This process repeats until none of the copies cause you to win
+/// any more cards.  (Cards will never make you copy a card past the end of the table.)
+///
+/// # Example:
+/// This time, the above example goes differently:
+/// ```text
+/// Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+/// Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+/// Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+/// Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+/// Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+/// Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+/// ```
+/// * Card 1 has four matching numbers, so you win one copy each of the next four cards: cards 2, 3, 4, and 5.
+/// * Your original card 2 has two matching numbers, so you win one copy each of cards 3 and 4.
+/// * Your copy of card 2 also wins one copy each of cards 3 and 4.
+/// * Your four instances of card 3 (one original and three copies) have two matching numbers, so you win **four** copies
+///   each of cards 3 and 4.
+/// * Your four instances of card 3 (one original and three copies) have two matching numbers, so you win **eight** copies
+///   of card 5.
+/// * Your fourteen instances of card 5 (one original and thirteen copies) have no matching numbers and win no more cards.
+/// * Your one instance of card 6 (one original) has no matching numbers and wins no more cards.
+///
+/// Once all of the originals and copies have been processed, you end up with **1** instance of card 1, **2** instances of
+/// card 2, **4** instances of card 3, **8** instances of card 4, **14** instances of card 5, and **1** instance of card
+/// 6.  In total, this example pile of scratchcards causes you to ultimately have **30** scratchcards!
+///
+/// Process all of the original and copied scratchcards until no more scratchcards are won.  Including the original set
+/// of scratchcards, **how many total scratchcards do you end up with?**
+pub fn part_2(input: &str) -> Result<usize> {
+    Ok(total_scratchcards(&parse(input)?))
+}
+
+/// The number of scratchcards you end up with.  A card with `n` matches wins the `n` cards whose
+/// numbers follow its own, wherever they appear in the pile; any of those that aren't in the pile
+/// at all, such as cards past the end of the table, are simply not won.
+///
+/// Every instance of a card wins the same cards, so rather than playing each copy separately the
+/// number of instances of a card is added to each card it wins, in card number order.  By the time a
+/// card is reached, every card before it has already added its copies, so one pass is enough.
+pub fn total_scratchcards(cards: &[Card]) -> usize {
+    let mut cards: Vec<&Card> = cards.iter().collect();
+    cards.sort_unstable_by_key(|card| card.id);
+    let mut copies: HashMap<usize, usize> = cards.iter().map(|card| (card.id, 1)).collect();
+
+    for card in cards {
+        let instances = copies[&card.id];
+        for won in card.id + 1..=card.id + card.matches() {
+            if let Some(copies) = copies.get_mut(&won) {
+                *copies += instances;
+            }
+        }
+    }
+    copies.into_values().sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+";
+
+    #[test]
+    fn part_1_example() {
+        assert_eq!(part_1(EXAMPLE).unwrap(), 13);
+    }
+
+    #[test]
+    fn part_2_example() {
+        assert_eq!(part_2(EXAMPLE).unwrap(), 30);
+    }
+
+    /// A card numbered `id` with `matches` matching numbers.
+    fn card(id: usize, matches: u32) -> Card {
+        Card { id, line: id, winning: (0..matches).collect(), have: (0..matches).collect() }
+    }
+
+    #[test]
+    fn copies_cascade_in_a_single_pass() {
+        // Each card wins one copy of the next, so card `n` ends up with `n` instances: billions of
+        // cards in total, far too many to play one at a time.
+        let n = 100_000;
+        let mut cards: Vec<Card> = (1..n).map(|id| card(id, 1)).collect();
+        cards.push(card(n, 0));
+        assert_eq!(total_scratchcards(&cards), n * (n + 1) / 2);
+    }
+
+    #[test]
+    fn copies_are_won_by_card_number() {
+        let input = "\
+Card 3: 1 | 2
+
+Card 1: 1 2 | 1 2
+Card 2: 5 | 5
+";
+        let cards = parse(input).unwrap();
+        assert_eq!(cards.iter().map(|card| card.id).collect::<Vec<_>>(), vec![3, 1, 2]);
+        // Card 1 wins cards 2 and 3; both instances of card 2 win another card 3.
+        assert_eq!(part_2(input).unwrap(), 1 + 2 + 4);
+        assert_eq!(part_1(input).unwrap(), 2 + 1);
+    }
+
+    #[test]
+    fn malformed_cards_are_reported_with_their_line() {
+        let err = parse("Card 1: 1 | 1\nCard 2: 3 4 5").unwrap_err();
+        assert!(matches!(err, AocError::Parse { line: 2, column: 8, .. }), "{}", err);
+        let err = parse("\n1 2 | 3").unwrap_err();
+        assert!(matches!(err, AocError::Parse { line: 2, column: 1, .. }), "{}", err);
+        let err = parse("Card 1: 1 | 1\nCard 1: 2 | 2").unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 1: card 1 is already listed on line 1");
+    }
+
+    #[test]
+    fn wins_past_the_end_of_the_table_are_clamped() {
+        // Card 2 has three matches but only card 3 follows it.
+        let input = "Card 1: 1 | 1\nCard 2: 1 2 3 | 1 2 3\nCard 3: 1 | 2\n";
+        assert_eq!(part_2(input).unwrap(), 1 + 2 + 3);
+
+        // Missing cards in the middle of the table are skipped the same way.
+        let cards = parse("Card 1: 1 2 | 1 2\nCard 3: 1 | 2").unwrap();
+        assert_eq!(total_scratchcards(&cards), 1 + 2);
+    }
+
+    #[test]
+    fn wins_past_the_end_of_the_table_can_be_rejected() {
+        let input = "Card 1: 1 | 1\n\nCard 2: 1 2 3 | 1 2 3\nCard 3: 1 | 2\n";
+        let err = parse_with_overflow(input, Overflow::Reject).unwrap_err();
+        assert_eq!(err.to_string(), "line 3, column 1: card 2 wins cards 3 to 5, but there is no card 4");
+
+        let err = parse_with_overflow("Card 1: 1 2 | 1 2\nCard 3: 1 | 2", Overflow::Reject).unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 1: card 1 wins cards 2 to 3, but there is no card 2");
+
+        let input = "Card 1: 1 2 | 1 2\nCard 2: 1 | 1\nCard 3: 1 | 2";
+        assert_eq!(parse_with_overflow(input, Overflow::Reject).unwrap(), parse(input).unwrap());
+    }
+
+    #[test]
+    fn points_past_usize_max_are_rejected() {
+        assert_eq!(card(1, 64).points(), Some(1 << 63));
+        assert_eq!(card(1, 65).points(), None);
+
+        let numbers: Vec<String> = (1..=70).map(|n| n.to_string()).collect();
+        let numbers = numbers.join(" ");
+        let input = format!("Card 1: 1 | 2\nCard 2: {} | {}", numbers, numbers);
+        let err = part_1(&input).unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 1: card 2 has 70 matches, too many points to count");
+        assert_eq!(part_2(&input).unwrap(), 2);
+    }
+}
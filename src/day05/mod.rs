@@ -0,0 +1,5 @@
+//! Day 5: If You Give A Seed A Fertilizer
+
+pub mod seed_fertilizer;
+
+pub use seed_fertilizer::SeedFertilizer;
@@ -0,0 +1,458 @@
+use std::ops::Range;
+use crate::error::{AocError, Result};
+use crate::solution::{Answer, Solution};
+
+/// Day 5: If You Give A Seed A Fertilizer
+pub struct SeedFertilizer;
+
+impl Solution for SeedFertilizer {
+    const DAY: u8 = 5;
+    const NAME: &'static str = "seed_fertilizer";
+
+    type Input = Almanac;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Result<Answer> {
+        Ok(lowest_location(input).into())
+    }
+
+    fn part_2(input: &Self::Input) -> Result<Answer> {
+        lowest_location_of_ranges(input).map(Answer::from)
+    }
+}
+
+/// One line of an almanac map: `length` numbers starting at `source` map onto the same number of
+/// values starting at `destination`.  [`parse`] makes sure neither range runs past `u64::MAX`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MapRange {
+    pub destination: u64,
+    pub source: u64,
+    pub length: u64,
+}
+
+impl MapRange {
+    fn source_end(&self) -> u64 {
+        self.source + self.length
+    }
+}
+
+/// A `<from>-to-<to> map:` section of the almanac.  The ranges are kept sorted by source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlmanacMap {
+    pub from: String,
+    pub to: String,
+    pub ranges: Vec<MapRange>,
+}
+
+impl AlmanacMap {
+    /// Converts a single source number into its destination number.
+    pub fn map(&self, value: u64) -> u64 {
+        self.ranges
+            .iter()
+            .find(|range| range.source <= value && value < range.source_end())
+            .map_or(value, |range| range.destination + (value - range.source))
+    }
+
+    /// Converts whole ranges of source numbers, splitting each one wherever it crosses the edge of
+    /// a map range.
+    pub fn map_ranges(&self, ranges: &[Range<u64>]) -> Vec<Range<u64>> {
+        let mut mapped = Vec::new();
+
+        for range in ranges {
+            let mut start = range.start;
+            for map_range in &self.ranges {
+                if start >= range.end || map_range.source >= range.end {
+                    break;
+                }
+                if map_range.source_end() <= start {
+                    continue;
+                }
+                // Anything before this map range isn't mapped at all.
+                if start < map_range.source {
+                    mapped.push(start..map_range.source);
+                    start = map_range.source;
+                }
+                let end = map_range.source_end().min(range.end);
+                let offset = start - map_range.source;
+                mapped.push(map_range.destination + offset..map_range.destination + offset + (end - start));
+                start = end;
+            }
+            if start < range.end {
+                mapped.push(start..range.end);
+            }
+        }
+
+        mapped
+    }
+}
+
+/// The Island Island Almanac: the seeds to plant and the chain of maps from seed to location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    /// The line the seeds are listed on, for reporting problems with them.
+    pub seeds_line: usize,
+    pub maps: Vec<AlmanacMap>,
+}
+
+/// Parses the `seeds:` line and each of the maps that follow it.
+pub fn parse(input: &str) -> Result<Almanac> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .map(|(line_num, line)| (line_num + 1, line))
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let (line_num, line) = lines
+        .next()
+        .ok_or_else(|| AocError::parse(1, 1, "expected a `seeds:` line"))?;
+    let seeds = line
+        .strip_prefix("seeds:")
+        .ok_or_else(|| AocError::parse(line_num, 1, "expected line to start with `seeds:`"))?;
+    let seeds = parse_numbers(line_num, line, seeds)?;
+    if seeds.is_empty() {
+        return Err(AocError::parse(line_num, line.len() + 1, "no seeds listed"));
+    }
+
+    let mut maps: Vec<AlmanacMap> = Vec::new();
+    for (line_num, line) in lines {
+        if let Some(header) = line.strip_suffix(" map:") {
+            let (from, to) = header
+                .split_once("-to-")
+                .ok_or_else(|| AocError::parse(line_num, 1, format!("expected `<from>-to-<to> map:`, found `{}`", line)))?;
+            let expected_from = maps.last().map_or("seed", |map| map.to.as_str());
+            if from != expected_from {
+                return Err(AocError::parse(
+                    line_num,
+                    1,
+                    format!("expected a map from `{}`, found one from `{}`", expected_from, from),
+                ));
+            }
+            maps.push(AlmanacMap { from: from.to_string(), to: to.to_string(), ranges: Vec::new() });
+            continue;
+        }
+
+        let map = maps
+            .last_mut()
+            .ok_or_else(|| AocError::parse(line_num, 1, "expected a map header before its ranges"))?;
+        match parse_numbers(line_num, line, line)?[..] {
+            [destination, source, length] => {
+                if destination.checked_add(length).is_none() || source.checked_add(length).is_none() {
+                    return Err(AocError::parse(line_num, 1, "range runs past the largest possible number"));
+                }
+                map.ranges.push(MapRange { destination, source, length })
+            }
+            _ => {
+                return Err(AocError::parse(
+                    line_num,
+                    1,
+                    "expected `<destination start> <source start> <length>`",
+                ))
+            }
+        }
+    }
+
+    for map in &mut maps {
+        map.ranges.sort_by_key(|range| range.source);
+    }
+
+    Ok(Almanac { seeds, seeds_line: line_num, maps })
+}
+
+/// Parses the whitespace-separated numbers in `numbers`, a slice of `line`.
+fn parse_numbers(line_num: usize, line: &str, numbers: &str) -> Result<Vec<u64>> {
+    numbers
+        .split_ascii_whitespace()
+        .map(|n| {
+            n.parse::<u64>()
+                .map_err(|err| AocError::parse_at(line_num, line, n, format!("invalid number `{}`: {}", n, err)))
+        })
+        .collect()
+}
+
+/// The lowest location reached by any of the almanac's seeds.
+pub fn lowest_location(almanac: &Almanac) -> u64 {
+    almanac
+        .seeds
+        .iter()
+        .map(|&seed| almanac.maps.iter().fold(seed, |value, map| map.map(value)))
+        .min()
+        .unwrap_or_default()
+}
+
+/// The lowest location reached by any seed when the `seeds:` line is read as `<start> <length>`
+/// pairs.  At least one of the ranges must hold a seed.
+pub fn lowest_location_of_ranges(almanac: &Almanac) -> Result<u64> {
+    let line_num = almanac.seeds_line;
+    if !almanac.seeds.len().is_multiple_of(2) {
+        return Err(AocError::parse(line_num, 1, "seed ranges must come in `<start> <length>` pairs"));
+    }
+
+    let seed_ranges: Vec<Range<u64>> = almanac
+        .seeds
+        .chunks(2)
+        .map(|pair| {
+            let end = pair[0].checked_add(pair[1]).ok_or_else(|| {
+                AocError::parse(line_num, 1, format!("seed range `{} {}` runs past the largest possible number", pair[0], pair[1]))
+            })?;
+            Ok(pair[0]..end)
+        })
+        .filter(|range| !matches!(range, Ok(range) if range.is_empty()))
+        .collect::<Result<_>>()?;
+    let locations = almanac
+        .maps
+        .iter()
+        .fold(seed_ranges, |ranges, map| map.map_ranges(&ranges));
+
+    locations
+        .iter()
+        .map(|range| range.start)
+        .min()
+        .ok_or_else(|| AocError::parse(line_num, 1, "every seed range is empty"))
+}
+
+/// You take the boat and find the gardener right where you were told he would be: managing a giant
+/// "garden" that looks more to you like a farm.
+///
+/// "A water source?  Island Island *is* the water source!"  You point out that Snow Island isn't
+/// receiving any water.
+///
+/// "Oh, we had to stop the water because we *ran out of sand* to filter it with!  Can't make snow
+/// with dirty water.  Don't worry, I'm sure we'll get more sand soon; we only turned off the water a
+/// few days... weeks... oh no."  His face sinks into a look of horrified realization.
+///
+/// "I've been so busy making sure everyone here has food that I completely forgot to check why we
+/// stopped getting more sand!  There's a ferry leaving soon that is headed over in that direction -
+/// it's much faster than your boat.  Could you please go check it out?"
+///
+/// You barely have time to agree to this request when he brings up another.  "While you wait for
+/// the ferry, maybe you can help us with our *food production problem*.  The latest Island Island
+/// Almanac just arrived and we're having trouble making sense of it."
+///
+/// The almanac (your puzzle input) lists all of the seeds that need to be planted.  It also lists
+/// what type of soil to use with each kind of seed, what type of fertilizer to use with each kind
+/// of soil, what type of water to use with each kind of fertilizer, and so on.  Every type of seed,
+/// soil, fertilizer and so on is identified with a number, but numbers are reused by each
+/// category - that is, soil `123` and fertilizer `123` aren't necessarily related to each other.
+///
+/// # Example
+///
+/// ```text
+/// seeds: 79 14 55 13
+///
+/// seed-to-soil map:
+/// 50 98 2
+/// 52 50 48
+///
+/// soil-to-fertilizer map:
+/// 0 15 37
+/// 37 52 2
+/// 39 0 15
+///
+/// fertilizer-to-water map:
+/// 49 53 8
+/// 0 11 42
+/// 42 0 7
+/// 57 7 4
+///
+/// water-to-light map:
+/// 88 18 7
+/// 18 25 70
+///
+/// light-to-temperature map:
+/// 45 77 23
+/// 81 45 19
+/// 68 64 13
+///
+/// temperature-to-humidity map:
+/// 0 69 1
+/// 1 0 69
+///
+/// humidity-to-location map:
+/// 60 56 37
+/// 56 93 4
+/// ```
+///
+/// The almanac starts by listing which seeds need to be planted: seeds `79`, `14`, `55`, and `13`.
+///
+/// The rest of the almanac contains a list of *maps* which describe how to convert numbers from a
+/// *source category* into numbers in a *destination category*.  That is, the section that starts
+/// with `seed-to-soil map:` describes how to convert a *seed number* (the source) to a *soil
+/// number* (the destination).  This lets the gardener and his team know which soil to use with
+/// which seeds, which water to use with which fertilizer, and so on.
+///
+/// Rather than list every source number and its corresponding destination number one by one, the
+/// maps describe entire *ranges* of numbers that can be converted.  Each line within a map contains
+/// three numbers: the *destination range start*, the *source range start*, and the *range length*.
+///
+/// Any source numbers that *aren't mapped* correspond to the *same* destination number.  So, seed
+/// number `10` corresponds to soil number `10`.
+///
+/// The gardener and his team want to get started as soon as possible, so they'd like to know the
+/// closest location that needs a seed.  Using these maps, find *the lowest location number that
+/// corresponds to any of the initial seeds*.  In this example, the corresponding types are:
+///
+/// * Seed `79`, soil `81`, fertilizer `81`, water `81`, light `74`, temperature `78`, humidity
+///   `78`, *location `82`*.
+/// * Seed `14`, soil `14`, fertilizer `53`, water `49`, light `42`, temperature `42`, humidity
+///   `43`, *location `43`*.
+/// * Seed `55`, soil `57`, fertilizer `57`, water `53`, light `46`, temperature `82`, humidity
+///   `82`, *location `86`*.
+/// * Seed `13`, soil `13`, fertilizer `52`, water `41`, light `34`, temperature `34`, humidity
+///   `35`, *location `35`*.
+///
+/// So, the lowest location number in this example is `35`.
+///
+/// *What is the lowest location number that corresponds to any of the initial seed numbers?*
+pub fn part_1(input: &str) -> Result<u64> {
+    Ok(lowest_location(&parse(input)?))
+}
+
+/// Everyone will starve if you only plant such a small number of seeds.  Re-reading the almanac,
+/// it looks like the `seeds:` line actually describes *ranges of seed numbers*.
+///
+/// The values on the initial `seeds:` line come in pairs.  Within each pair, the first value is the
+/// *start* of the range and the second value is the *length* of the range.  So, in the first line
+/// of the example above:
+///
+/// ```text,excerpt
+/// seeds: 79 14 55 13
+/// ```
+///
+/// This line describes two ranges of seed numbers to be planted in the garden.  The first range
+/// starts with seed number `79` and contains `14` values: `79`, `80`, ..., `91`, `92`.  The second
+/// range starts with seed number `55` and contains `13` values: `55`, `56`, ..., `66`, `67`.
+///
+/// Now, rather than considering four seed numbers, you need to consider a total of *27* seed
+/// numbers.
+///
+/// In the above example, the lowest location number can be obtained from seed number `82`, which
+/// corresponds to soil `84`, fertilizer `84`, water `84`, light `77`, temperature `45`, humidity
+/// `46`, and *location `46`*.  So, the lowest location number is `46`.
+///
+/// Consider all of the initial seed numbers listed in the ranges on the first line of the almanac.
+/// *What is the lowest location number that could correspond to any of the initial seed numbers?*
+pub fn part_2(input: &str) -> Result<u64> {
+    lowest_location_of_ranges(&parse(input)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
+
+    #[test]
+    fn part_1_example() {
+        assert_eq!(part_1(EXAMPLE).unwrap(), 35);
+    }
+
+    #[test]
+    fn part_2_example() {
+        assert_eq!(part_2(EXAMPLE).unwrap(), 46);
+    }
+
+    fn map(ranges: &[(u64, u64, u64)]) -> AlmanacMap {
+        let mut ranges: Vec<MapRange> = ranges
+            .iter()
+            .map(|&(destination, source, length)| MapRange { destination, source, length })
+            .collect();
+        ranges.sort_by_key(|range| range.source);
+        AlmanacMap { from: "seed".to_string(), to: "soil".to_string(), ranges }
+    }
+
+    fn map_range(map: &AlmanacMap, range: Range<u64>) -> Vec<Range<u64>> {
+        map.map_ranges(&[range])
+    }
+
+    #[test]
+    fn ranges_straddling_a_map_range_are_split() {
+        // 10..20 maps onto 100..110.
+        let map = map(&[(100, 10, 10)]);
+        assert_eq!(map_range(&map, 5..15), vec![5..10, 100..105]);
+        assert_eq!(map_range(&map, 15..25), vec![105..110, 20..25]);
+        assert_eq!(map_range(&map, 5..25), vec![5..10, 100..110, 20..25]);
+    }
+
+    #[test]
+    fn ranges_in_an_unmapped_gap_are_unchanged() {
+        let map = map(&[(100, 10, 10), (200, 30, 10)]);
+        assert_eq!(map_range(&map, 20..30), vec![20..30]);
+        assert_eq!(map.map_ranges(&[0..5, 50..60]), vec![0..5, 50..60]);
+    }
+
+    #[test]
+    fn ranges_spanning_adjacent_map_ranges_are_split_at_each() {
+        let map = map(&[(200, 20, 10), (100, 10, 10), (0, 30, 5)]);
+        assert_eq!(map_range(&map, 12..33), vec![102..110, 200..210, 0..3]);
+        assert_eq!(map_range(&map, 12..40), vec![102..110, 200..210, 0..5, 35..40]);
+        for value in 12..40 {
+            let mapped = map_range(&map, value..value + 1);
+            assert_eq!(mapped, vec![map.map(value)..map.map(value) + 1]);
+        }
+    }
+
+    #[test]
+    fn numbers_past_u64_max_are_rejected() {
+        let max = u64::MAX;
+        let err = parse(&format!("seeds: 1\nseed-to-soil map:\n{} 0 2", max)).unwrap_err();
+        assert_eq!(err.to_string(), "line 3, column 1: range runs past the largest possible number");
+        assert!(parse(&format!("seeds: 1\nseed-to-soil map:\n0 {} 2", max)).is_err());
+
+        let almanac = parse(&format!("\nseeds: {} 2\nseed-to-soil map:\n0 0 1", max)).unwrap();
+        let err = lowest_location_of_ranges(&almanac).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("line 2, column 1: seed range `{} 2` runs past the largest possible number", max),
+        );
+    }
+
+    #[test]
+    fn seed_ranges_must_hold_a_seed() {
+        let almanac = parse("\n\nseeds: 5 0 7 0\nseed-to-soil map:\n0 0 1").unwrap();
+        assert_eq!(almanac.seeds_line, 3);
+        let err = lowest_location_of_ranges(&almanac).unwrap_err();
+        assert_eq!(err.to_string(), "line 3, column 1: every seed range is empty");
+
+        let err = part_2("\nseeds: 5 1 7\nseed-to-soil map:\n0 0 1").unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 1: seed ranges must come in `<start> <length>` pairs");
+        assert_eq!(part_2("seeds: 5 0 7 1\nseed-to-soil map:\n0 0 1").unwrap(), 7);
+    }
+}
@@ -0,0 +1,5 @@
+//! Day 6: Wait For It
+
+pub mod wait_for_it;
+
+pub use wait_for_it::WaitForIt;
@@ -0,0 +1,338 @@
+use crate::error::{column_of, AocError, Result};
+use crate::solution::{Answer, Solution};
+
+/// Day 6: Wait For It
+pub struct WaitForIt;
+
+impl Solution for WaitForIt {
+    const DAY: u8 = 6;
+    const NAME: &'static str = "wait_for_it";
+
+    type Input = RaceSheet;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Result<Answer> {
+        Ok(margin_of_error(&input.races).into())
+    }
+
+    fn part_2(input: &Self::Input) -> Result<Answer> {
+        Ok(input.kerned()?.ways_to_win().into())
+    }
+}
+
+/// One boat race: how long it lasts (in milliseconds) and the record distance to beat (in
+/// millimeters).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Race {
+    pub time: u64,
+    pub distance: u64,
+}
+
+impl Race {
+    /// The number of whole milliseconds you could hold the button for and beat the record.
+    ///
+    /// Holding for `h` milliseconds travels `h * (time - h)`, so the winning hold times are the
+    /// integers strictly between the roots of `h² - time·h + distance = 0`.  The roots are estimated
+    /// with floating point and then nudged onto the exact boundary with integer arithmetic, so this
+    /// stays exact for times far beyond what `f64` can represent precisely.
+    pub fn ways_to_win(&self) -> u64 {
+        let (time, distance) = (self.time as u128, self.distance as u128);
+        let beats_record = |hold: u128| hold <= time && hold * (time - hold) > distance;
+
+        let discriminant = (time * time).checked_sub(4 * distance);
+        let Some(discriminant) = discriminant else {
+            return 0;
+        };
+        let root = (time as f64 - (discriminant as f64).sqrt()) / 2.0;
+
+        let mut shortest = (root.max(0.0) as u128).min(time / 2);
+        while shortest > 0 && beats_record(shortest - 1) {
+            shortest -= 1;
+        }
+        while shortest <= time / 2 && !beats_record(shortest) {
+            shortest += 1;
+        }
+        if shortest > time / 2 {
+            return 0;
+        }
+
+        // Distances are symmetric around `time / 2`, so the longest winning hold mirrors the shortest.
+        (time - 2 * shortest + 1) as u64
+    }
+}
+
+/// The sheet of paper handed out when signing up for the races.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RaceSheet {
+    /// Each column of the sheet read as its own race.
+    pub races: Vec<Race>,
+    /// The whole sheet read as a single race; see [`RaceSheet::kerned`].
+    kerned: Deferred<Race>,
+}
+
+/// A value whose parse error is only raised once it's asked for, kept as the error's line, column
+/// and message since [`AocError`] can't be cloned.
+type Deferred<T> = std::result::Result<T, (usize, usize, String)>;
+
+impl RaceSheet {
+    /// The whole sheet read as a single race, ignoring the spaces between numbers.  Reading the
+    /// numbers this way can overflow where the separate races don't, so the error is only raised
+    /// here rather than by [`parse`].
+    pub fn kerned(&self) -> Result<Race> {
+        self.kerned.clone().map_err(|(line, column, message)| AocError::parse(line, column, message))
+    }
+}
+
+/// Parses the `Time:` and `Distance:` lines of a race sheet.
+pub fn parse(input: &str) -> Result<RaceSheet> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    // Where a missing line would have been.
+    let end = input.lines().count() + 1;
+
+    let (_, times, kerned_time) = parse_row(lines.next(), end, "Time:")?;
+    let (line_num, distances, kerned_distance) = parse_row(lines.next(), end, "Distance:")?;
+    if times.len() != distances.len() {
+        return Err(AocError::parse(
+            line_num,
+            1,
+            format!("{} race times but {} record distances", times.len(), distances.len()),
+        ));
+    }
+    if let Some((line_num, line)) = lines.next() {
+        return Err(AocError::parse(line_num + 1, 1, format!("unexpected line `{}`", line)));
+    }
+
+    let races = times
+        .into_iter()
+        .zip(distances)
+        .map(|(time, distance)| Race { time, distance })
+        .collect();
+    let kerned = kerned_time.and_then(|time| Ok(Race { time, distance: kerned_distance? }));
+
+    Ok(RaceSheet { races, kerned })
+}
+
+/// Parses one `<label> n n n ...` row, returning its line number and the separate numbers along
+/// with all of their digits run together, or where and why that number is invalid.  `end` is the
+/// line number reported if the row is missing.
+fn parse_row(row: Option<(usize, &str)>, end: usize, label: &str) -> Result<(usize, Vec<u64>, Deferred<u64>)> {
+    let Some((line_num, line)) = row else {
+        return Err(AocError::parse(end, 1, format!("missing `{}` line", label)));
+    };
+    let line_num = line_num + 1;
+    let values = line
+        .strip_prefix(label)
+        .ok_or_else(|| AocError::parse(line_num, 1, format!("expected line to start with `{}`", label)))?;
+
+    let numbers = values
+        .split_ascii_whitespace()
+        .map(|n| {
+            n.parse::<u64>()
+                .map_err(|err| AocError::parse_at(line_num, line, n, format!("invalid number `{}`: {}", n, err)))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if numbers.is_empty() {
+        return Err(AocError::parse(line_num, line.len() + 1, format!("no numbers after `{}`", label)));
+    }
+
+    let kerned: String = values.split_ascii_whitespace().collect();
+    let kerned = kerned.parse::<u64>().map_err(|err| {
+        (line_num, column_of(line, values.trim_start()), format!("kerned number `{}` is invalid: {}", kerned, err))
+    });
+
+    Ok((line_num, numbers, kerned))
+}
+
+/// The product of the number of ways to win each of `races`.
+pub fn margin_of_error(races: &[Race]) -> u64 {
+    races.iter().map(Race::ways_to_win).product()
+}
+
+/// The ferry quickly brings you across Island Island. After asking around, you discover that there
+/// is indeed normally a large pile of sand somewhere near here, but you don't see anything besides
+/// lots of water and the small island where the ferry has docked.
+///
+/// As you try to figure out what to do next, you notice a poster on a wall near the ferry dock.
+/// "Boat races! Open to the public! Grand prize is an all-expenses-paid trip to **Desert Island!**"
+/// That must be where the sand comes from! Best of all, the boat races are starting in just a few
+/// minutes.
+///
+/// You manage to sign up as a competitor in the boat races just in time. The organizer explains
+/// that it's not really a traditional race - instead, you will get a fixed amount of time during
+/// which your boat has to travel as far as it can, and you win if your boat goes the farthest.
+///
+/// As part of signing up, you get a sheet of paper (your puzzle input) that lists the **time**
+/// allowed for each race and also the best **distance** ever recorded in that race. To guarantee
+/// you win the grand prize, you need to make sure you **go farther in each race** than the current
+/// record holder.
+///
+/// The organizer brings you over to the area where the boat races are held. The boats are much
+/// smaller than you expected - they're actually **toy boats**, each with a big button on top.
+/// Holding down the button **charges the boat**, and releasing the button **allows the boat to
+/// move.** Boats move faster if their button was held longer, but time spent holding the button
+/// counts against the total race time. You can only hold the button at the start of the race, and
+/// boats don't move until the button is released.
+///
+/// # Example:
+/// ```text
+/// Time:      7  15   30
+/// Distance:  9  40  200
+/// ```
+/// This document describes three races:
+/// * The first race lasts 7 milliseconds.  The record distance in this race is 9 millimeters.
+/// * The second race lasts 15 milliseconds.  The record distance in this race is 40 millimeters.
+/// * The third race lasts 30 milliseconds.  The record distance in this race is 200 millimeters.
+///
+/// Your toy boat has a starting speed of **zero millimeters per millisecond**.  For each whole
+/// millisecond you spend at the beginning of the race holding the button, the boat's speed
+/// increases by **one millimeter per millisecond**.
+///
+/// So, because the first race lasts 7 milliseconds, you only have a few options:
+/// * Don't hold the button at all (that is, hold it for **`0` milliseconds**) at the start of the
+///   race.  The boat won't move; it will have traveled **`0` millimeters** by the end of the race.
+/// * Hold the button for **`1` millisecond** at the start of the race.  Then, the boat will travel
+///   at a speed of `1` millimeter per millisecond for 6 milliseconds, reaching a total distance of
+///   **`6` millimeters**.
+/// * Hold the button for **`2` milliseconds**, giving the boat a speed of 2 millimeters per
+///   millisecond. It will then get 5 milliseconds to move, reaching a total distance of **`10`
+///   millimeters**.
+/// * Hold the button for **`3` milliseconds**. After its remaining 4 milliseconds of travel time,
+///   the boat will have gone **`12` millimeters**.
+/// * Hold the button for **`4` milliseconds**. After its remaining 3 milliseconds of travel time,
+///   the boat will have gone **`12` millimeters**.
+/// * Hold the button for **`5` milliseconds**, causing the boat to travel a total of **`10`
+///   millimeters**.
+/// * Hold the button for **`6` milliseconds**, causing the boat to travel a total of **`6`
+///   millimeters**.
+/// * Hold the button for **`7` milliseconds**. That's the entire duration of the race. You never
+///   let go of the button. The boat can't move until you let go of the button. Please make sure
+///   you let go of the button so the boat gets to move.**` 0` millimeters**.
+///
+/// Since the current record for this race is 9 millimeters, there are actually **`4`** different
+/// ways you could win: you could hold the button for `2`, `3`, `4`, or `5` milliseconds at the
+/// start of the race.
+///
+/// In the second race, you could hold the button for at least `4` milliseconds and at most `11`
+/// milliseconds and beat the record, a total of **`8`** different ways to win.
+///
+/// In the third race, you could hold the button for at least `11` milliseconds and no more than
+/// `19` milliseconds and still beat the record, a total of **`9`** ways you could win.
+///
+/// To see how much margin of error you have, **determine the number of ways you can beat the
+/// record** in each race; in this example, if you multiply these values together, you get **288**
+/// (`4` * `8` * `9`).
+///
+/// Determine the number of ways you could beat the record in each race. **What do you get if you
+/// multiply these numbers together?**
+pub fn part_1(input: &str) -> Result<u64> {
+    Ok(margin_of_error(&parse(input)?.races))
+}
+
+/// As the race is about to start, you realize the piece of paper with race times and record
+/// distances you got earlier actually just has very bad kerning.  There's really only
+/// **one race** - ignore the spaces between the numbers on each line.
+///
+/// So, the example from before:
+/// ```text
+/// Time:      7  15   30
+/// Distance:  9  40  200
+/// ```
+/// ...now instead means this:
+/// ```text
+/// Time:      71530
+/// Distance:  940200
+/// ```
+/// Now, you have to figure out how many ways there are to win this single race.  In this example,
+/// the race lasts for **`71530` milliseconds** and the record distance you need to beat is
+/// **`940200` millimeters**.  You could hold the button anywhere from `14` to `71516` milliseconds
+/// and beat the record, a total of **`71503`** ways!
+///
+/// **How many ways can you beat the record in this one much longer race?**
+pub fn part_2(input: &str) -> Result<u64> {
+    Ok(parse(input)?.kerned()?.ways_to_win())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Time:      7  15   30
+Distance:  9  40  200
+";
+
+    #[test]
+    fn part_1_example() {
+        assert_eq!(part_1(EXAMPLE).unwrap(), 288);
+    }
+
+    #[test]
+    fn part_2_example() {
+        assert_eq!(part_2(EXAMPLE).unwrap(), 71503);
+    }
+
+    fn race(time: u64, distance: u64) -> Race {
+        Race { time, distance }
+    }
+
+    fn brute_force(race: Race) -> u64 {
+        (0..=race.time).filter(|hold| hold * (race.time - hold) > race.distance).count() as u64
+    }
+
+    #[test]
+    fn small_races_match_brute_force() {
+        for time in 0..60 {
+            for distance in 0..=time * time / 4 + 1 {
+                let race = race(time, distance);
+                assert_eq!(race.ways_to_win(), brute_force(race), "{:?}", race);
+            }
+        }
+        assert_eq!(race(71530, 940200).ways_to_win(), brute_force(race(71530, 940200)));
+    }
+
+    #[test]
+    fn huge_races_are_exact() {
+        // Holding for exactly half the race is the only way to beat a record one short of the best
+        // possible distance; `f64` can't tell these distances apart from their neighbours.
+        let half = 2_000_000_000;
+        assert_eq!(race(2 * half, half * half - 1).ways_to_win(), 1);
+        assert_eq!(race(2 * half, half * half).ways_to_win(), 0);
+        assert_eq!(race(2 * half + 1, half * (half + 1) - 1).ways_to_win(), 2);
+        assert_eq!(race(2 * half + 1, half * (half + 1)).ways_to_win(), 0);
+        assert_eq!(race(u64::MAX, 0).ways_to_win(), u64::MAX - 1);
+    }
+
+    #[test]
+    fn unbeatable_records_have_no_ways_to_win() {
+        // A zero discriminant: the best possible distance only equals the record.
+        assert_eq!(race(4, 4).ways_to_win(), 0);
+        // `time² < 4 * distance`: the record is out of reach altogether.
+        assert_eq!(race(4, 5).ways_to_win(), 0);
+        assert_eq!(race(0, 0).ways_to_win(), 0);
+    }
+
+    #[test]
+    fn records_at_integer_roots_are_not_wins() {
+        // Holding for 2 or 8 of 10 milliseconds only ties the record of 16.
+        assert_eq!(race(10, 16).ways_to_win(), 5);
+        // Holding for 2 or 5 of 7 milliseconds only ties the record of 10.
+        assert_eq!(race(7, 10).ways_to_win(), 2);
+    }
+
+    #[test]
+    fn kerned_overflow_only_fails_part_2() {
+        let input = "Time: 1000 1000 1000 1000 1000 1000\nDistance: 1 1 1 1 1 1\n";
+        assert_eq!(part_1(input).unwrap(), 999u64.pow(6));
+        assert_eq!(
+            part_2(input).unwrap_err().to_string(),
+            "line 1, column 7: kerned number `100010001000100010001000` is invalid: number too large to fit in target type",
+        );
+    }
+}
@@ -0,0 +1,158 @@
+//! Checks the worked examples in each day's documentation against its solver.
+//!
+//! The doc comment on every `part_1`/`part_one` and `part_2`/`part_two` function retells the
+//! puzzle, including an example input in a fenced block tagged `text` and the answer it should
+//! produce.  The answer is taken to be the last number in the prose that's set in backticks or
+//! emphasis, ignoring anything in parentheses, e.g. the `288` in "you get **288** (`4` * `8` *
+//! `9`)".  Fragments of an earlier example are tagged `text,excerpt` and skipped; a part whose docs
+//! have no example of their own reuses the previous part's.
+
+use crate::registry::{self, PUZZLES};
+
+/// The source of each day's solver.
+const SOURCES: &[(u8, &str)] = &[
+    (1, include_str!("day01/trebuchet.rs")),
+    (2, include_str!("day02/cube_conundrum.rs")),
+    (3, include_str!("day03/gear_ratios.rs")),
+    (4, include_str!("day04/scratchcards.rs")),
+    (5, include_str!("day05/seed_fertilizer.rs")),
+    (6, include_str!("day06/wait_for_it.rs")),
+];
+
+/// A worked example from the docs of one part.
+#[derive(Debug, PartialEq, Eq)]
+struct Example {
+    part: u8,
+    input: String,
+    answer: String,
+}
+
+/// The worked examples documented in `source`, one per part.
+fn examples(source: &str) -> Vec<Example> {
+    let mut examples: Vec<Example> = Vec::new();
+    let mut doc = Vec::new();
+
+    for line in source.lines() {
+        let line = line.trim_start();
+        if let Some(text) = line.strip_prefix("///") {
+            doc.push(text.strip_prefix(' ').unwrap_or(text));
+            continue;
+        }
+        let part = match line.split('(').next() {
+            Some("pub fn part_1" | "pub fn part_one") => Some(1),
+            Some("pub fn part_2" | "pub fn part_two") => Some(2),
+            _ => None,
+        };
+        if let Some(part) = part {
+            let (input, prose) = split_doc(&doc);
+            let input = input
+                .or_else(|| examples.last().map(|example| example.input.clone()))
+                .unwrap_or_else(|| panic!("part {} has no example input", part));
+            let answer = stated_answer(&prose).unwrap_or_else(|| panic!("part {} states no answer", part));
+            examples.push(Example { part, input, answer });
+        }
+        doc.clear();
+    }
+
+    examples
+}
+
+/// Splits a doc comment into its first `text` block, if any, and the prose outside of its blocks.
+fn split_doc(doc: &[&str]) -> (Option<String>, String) {
+    let mut input = None;
+    let mut prose = String::new();
+    let mut block: Option<(bool, String)> = None;
+
+    for line in doc {
+        match (line.strip_prefix("```"), block.take()) {
+            (Some(info), None) => block = Some((info.trim() == "text", String::new())),
+            (Some(_), Some((is_example, text))) => {
+                if is_example && input.is_none() {
+                    input = Some(text);
+                }
+            }
+            (None, Some((is_example, mut text))) => {
+                text.push_str(line);
+                text.push('\n');
+                block = Some((is_example, text));
+            }
+            (None, None) => {
+                prose.push_str(line);
+                prose.push('\n');
+            }
+        }
+    }
+
+    (input, prose)
+}
+
+/// The last number in `prose` that's set in backticks or emphasis, outside of any parentheses.
+fn stated_answer(prose: &str) -> Option<String> {
+    let mut outside = String::new();
+    let mut depth = 0usize;
+    for c in prose.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            c if depth == 0 => outside.push(c),
+            _ => {}
+        }
+    }
+
+    let is_delimiter = |c: Option<char>| matches!(c, Some('`' | '*'));
+    let mut answer = None;
+    let mut rest = outside.as_str();
+    while let Some(start) = rest.find(|c: char| c.is_ascii_digit()) {
+        let len = rest[start..].find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len() - start);
+        let number = &rest[start..start + len];
+        if is_delimiter(rest[..start].chars().next_back()) && is_delimiter(rest[start + len..].chars().next()) {
+            answer = Some(number.to_string());
+        }
+        rest = &rest[start + len..];
+    }
+    answer
+}
+
+#[test]
+fn documented_examples_match_the_solvers() {
+    for &(day, source) in SOURCES {
+        let puzzle = registry::find(day).unwrap_or_else(|| panic!("day {} isn't registered", day));
+        let examples = examples(source);
+        assert_eq!(
+            examples.iter().map(|example| example.part).collect::<Vec<_>>(),
+            vec![1, 2],
+            "day {} should document both parts",
+            day,
+        );
+
+        for example in examples {
+            let report = (puzzle.run)(&example.input, &[example.part])
+                .unwrap_or_else(|err| panic!("day {} part {} example: {}", day, example.part, err));
+            let answer = report.parts[0]
+                .answer
+                .as_ref()
+                .unwrap_or_else(|err| panic!("day {} part {} example: {}", day, example.part, err));
+            assert_eq!(answer.to_string(), example.answer, "day {} part {} example", day, example.part);
+        }
+    }
+}
+
+#[test]
+fn every_puzzle_has_its_source_checked() {
+    for puzzle in PUZZLES {
+        assert!(
+            SOURCES.iter().any(|&(day, _)| day == puzzle.day),
+            "day {} ({}) has no entry in SOURCES",
+            puzzle.day,
+            puzzle.name,
+        );
+    }
+}
+
+#[test]
+fn answers_are_read_from_the_prose() {
+    assert_eq!(stated_answer("you get **288** (`4` * `8` * `9`).").as_deref(), Some("288"));
+    assert_eq!(stated_answer("a total of **`71503`** ways!").as_deref(), Some("71503"));
+    assert_eq!(stated_answer("games 1, 2, and 5; you get *8*.").as_deref(), Some("8"));
+    assert_eq!(stated_answer("no answer here, only 12 red cubes").as_deref(), None);
+}
@@ -0,0 +1,65 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use crate::input::{Source, INPUT_DIR_VAR};
+
+/// Everything that can go wrong while loading, parsing or solving a puzzle.
+#[derive(Debug)]
+pub enum AocError {
+    /// None of the candidate locations for a day's input exist.
+    InputNotFound { day: u8, tried: Vec<PathBuf> },
+    /// An input was found but couldn't be read.
+    Io { input: Source, error: io::Error },
+    /// The puzzle input is malformed.  `line` and `column` are 1-based.
+    Parse { line: usize, column: usize, message: String },
+    /// The requested part hasn't been solved yet.
+    Unsolved { day: u8, part: u8 },
+}
+
+/// A `Result` whose error is an [`AocError`].
+pub type Result<T> = std::result::Result<T, AocError>;
+
+impl AocError {
+    pub fn parse(line: usize, column: usize, message: impl Into<String>) -> AocError {
+        AocError::Parse { line, column, message: message.into() }
+    }
+
+    /// A parse error for `token`, which must be a slice of `line`; the column is worked out from
+    /// where `token` sits within `line`.
+    pub fn parse_at(line_num: usize, line: &str, token: &str, message: impl Into<String>) -> AocError {
+        AocError::parse(line_num, column_of(line, token), message)
+    }
+}
+
+/// The 1-based column at which `token`, a slice of `line`, starts.
+pub(crate) fn column_of(line: &str, token: &str) -> usize {
+    (token.as_ptr() as usize).saturating_sub(line.as_ptr() as usize) + 1
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::InputNotFound { day, tried } => {
+                write!(f, "no input found for day {}; tried:", day)?;
+                for path in tried {
+                    write!(f, "\n  {}", path.display())?;
+                }
+                write!(f, "\npass a path (or `-` for stdin), or set {}", INPUT_DIR_VAR)
+            }
+            AocError::Io { input, error } => write!(f, "unable to read {}: {}", input, error),
+            AocError::Parse { line, column, message } => {
+                write!(f, "line {}, column {}: {}", line, column, message)
+            }
+            AocError::Unsolved { day, part } => write!(f, "day {} part {} is not yet solved", day, part),
+        }
+    }
+}
+
+impl std::error::Error for AocError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AocError::Io { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
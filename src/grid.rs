@@ -0,0 +1,331 @@
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+use crate::error::{AocError, Result};
+
+/// A (row, column) position within a [`Grid`].  Row `0` is the top row and column `0` the leftmost
+/// column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Pos {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Pos {
+    pub const fn new(row: usize, col: usize) -> Pos {
+        Pos { row, col }
+    }
+}
+
+/// A direction to step in from one cell to the next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Dir {
+    /// The four horizontal and vertical directions, clockwise from up.
+    pub const CARDINAL: [Dir; 4] = [Dir::Up, Dir::Right, Dir::Down, Dir::Left];
+
+    /// All eight directions, clockwise from up.
+    pub const ALL: [Dir; 8] = [
+        Dir::Up,
+        Dir::UpRight,
+        Dir::Right,
+        Dir::DownRight,
+        Dir::Down,
+        Dir::DownLeft,
+        Dir::Left,
+        Dir::UpLeft,
+    ];
+
+    /// The (row, column) offset of one step in this direction.
+    pub const fn offset(self) -> (isize, isize) {
+        match self {
+            Dir::Up => (-1, 0),
+            Dir::Down => (1, 0),
+            Dir::Left => (0, -1),
+            Dir::Right => (0, 1),
+            Dir::UpLeft => (-1, -1),
+            Dir::UpRight => (-1, 1),
+            Dir::DownLeft => (1, -1),
+            Dir::DownRight => (1, 1),
+        }
+    }
+}
+
+/// What happens when a step would leave the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Edges {
+    /// Positions past an edge don't exist.
+    #[default]
+    Bounded,
+    /// Stepping past an edge comes back in on the opposite side.
+    Wrapping,
+}
+
+/// A rectangular grid of cells, stored row by row so any cell can be looked up directly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+    edges: Edges,
+}
+
+impl<T> Grid<T> {
+    /// Builds a bounded grid from its rows, all of which must be the same length.  Returns `None`
+    /// if they aren't.
+    pub fn from_rows(rows: impl IntoIterator<Item = Vec<T>>) -> Option<Grid<T>> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+
+        for row in rows {
+            if row.len() != *width.get_or_insert(row.len()) {
+                return None;
+            }
+            cells.extend(row);
+            height += 1;
+        }
+
+        Some(Grid { cells, width: width.unwrap_or(0), height, edges: Edges::Bounded })
+    }
+
+    /// Switches how the grid treats steps off its edges.
+    pub fn with_edges(mut self, edges: Edges) -> Grid<T> {
+        self.edges = edges;
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn edges(&self) -> Edges {
+        self.edges
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.row < self.height && pos.col < self.width
+    }
+
+    /// The cell at `pos`, or `None` if that's outside the grid.
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos).then(|| &self.cells[pos.row * self.width + pos.col])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.row * self.width + pos.col])
+        } else {
+            None
+        }
+    }
+
+    /// The position one step from `pos` in `dir`.  In a bounded grid this is `None` past an edge;
+    /// in a wrapping grid it comes back in on the other side.
+    pub fn step(&self, pos: Pos, dir: Dir) -> Option<Pos> {
+        let (d_row, d_col) = dir.offset();
+        match self.edges {
+            Edges::Bounded => {
+                let next = Pos::new(pos.row.checked_add_signed(d_row)?, pos.col.checked_add_signed(d_col)?);
+                self.contains(next).then_some(next)
+            }
+            Edges::Wrapping => {
+                if !self.contains(pos) {
+                    return None;
+                }
+                let wrap = |value: usize, delta: isize, len: usize| {
+                    (value as isize + delta).rem_euclid(len as isize) as usize
+                };
+                Some(Pos::new(wrap(pos.row, d_row, self.height), wrap(pos.col, d_col, self.width)))
+            }
+        }
+    }
+
+    /// The positions directly above, right of, below and left of `pos`.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Dir::CARDINAL.into_iter().filter_map(move |dir| self.step(pos, dir))
+    }
+
+    /// The positions surrounding `pos`, including diagonals.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Dir::ALL.into_iter().filter_map(move |dir| self.step(pos, dir))
+    }
+
+    /// Each row of the grid, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `max(1)` keeps `chunks` happy for an empty grid, which has no cells to chunk anyway.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        (row < self.height).then(|| &self.cells[row * self.width..(row + 1) * self.width])
+    }
+
+    /// The cells of column `col`, top to bottom.  Empty if `col` is outside the grid.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        let cells = if col < self.width { &self.cells[col..] } else { &[] };
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    /// Each column of the grid, left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |col| self.column(col))
+    }
+
+    /// Every position in the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| Pos::new(row, col)))
+    }
+
+    /// Every cell in the grid along with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// A grid of the same shape with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+            edges: self.edges,
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside a {}x{} grid", pos, self.height, self.width))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (height, width) = (self.height, self.width);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside a {}x{} grid", pos, height, width))
+    }
+}
+
+/// Parses one cell per byte of each line.  Every line must be the same length.
+impl<T: From<u8>> FromStr for Grid<T> {
+    type Err = AocError;
+
+    fn from_str(input: &str) -> Result<Grid<T>> {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = None;
+        let mut height = 0;
+
+        for (row_num, line) in input.lines().enumerate() {
+            let expected = *width.get_or_insert(line.len());
+            if line.len() != expected {
+                return Err(AocError::parse(
+                    row_num + 1,
+                    line.len().min(expected) + 1,
+                    format!("expected {} columns, found {}", expected, line.len()),
+                ));
+            }
+            cells.extend(line.bytes().map(T::from));
+            height += 1;
+        }
+
+        Ok(Grid { cells, width: width.unwrap_or(0), height, edges: Edges::Bounded })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(input: &str) -> Grid<u8> {
+        input.parse().unwrap()
+    }
+
+    #[test]
+    fn bounded_steps_stop_at_the_edges() {
+        let grid = grid("abc\ndef");
+        assert_eq!(grid.step(Pos::new(0, 0), Dir::Up), None);
+        assert_eq!(grid.step(Pos::new(0, 0), Dir::Left), None);
+        assert_eq!(grid.step(Pos::new(1, 2), Dir::DownRight), None);
+        assert_eq!(grid.step(Pos::new(0, 1), Dir::DownLeft), Some(Pos::new(1, 0)));
+    }
+
+    #[test]
+    fn wrapping_steps_come_back_in_on_the_other_side() {
+        let grid = grid("abc\ndef").with_edges(Edges::Wrapping);
+        assert_eq!(grid.step(Pos::new(0, 0), Dir::Up), Some(Pos::new(1, 0)));
+        assert_eq!(grid.step(Pos::new(0, 0), Dir::Left), Some(Pos::new(0, 2)));
+        assert_eq!(grid.step(Pos::new(1, 2), Dir::DownRight), Some(Pos::new(0, 0)));
+        assert_eq!(grid.step(Pos::new(2, 0), Dir::Up), None);
+    }
+
+    #[test]
+    fn wrapping_a_one_wide_grid_stays_in_its_column() {
+        let grid = grid("a\nb\nc").with_edges(Edges::Wrapping);
+        assert_eq!(grid.step(Pos::new(1, 0), Dir::Left), Some(Pos::new(1, 0)));
+        assert_eq!(grid.step(Pos::new(1, 0), Dir::Right), Some(Pos::new(1, 0)));
+        assert_eq!(grid.step(Pos::new(0, 0), Dir::UpRight), Some(Pos::new(2, 0)));
+        // Left and right both lead straight back to the cell itself.
+        assert_eq!(
+            grid.neighbors4(Pos::new(0, 0)).collect::<Vec<_>>(),
+            vec![Pos::new(2, 0), Pos::new(0, 0), Pos::new(1, 0), Pos::new(0, 0)],
+        );
+    }
+
+    #[test]
+    fn corners_have_fewer_neighbors() {
+        let grid = grid("abc\ndef\nghi");
+        assert_eq!(
+            grid.neighbors4(Pos::new(0, 0)).collect::<Vec<_>>(),
+            vec![Pos::new(0, 1), Pos::new(1, 0)],
+        );
+        assert_eq!(
+            grid.neighbors8(Pos::new(2, 2)).collect::<Vec<_>>(),
+            vec![Pos::new(1, 2), Pos::new(2, 1), Pos::new(1, 1)],
+        );
+        assert_eq!(grid.neighbors8(Pos::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbors4(Pos::new(0, 2)).count(), 2);
+    }
+
+    #[test]
+    fn columns_are_read_top_to_bottom() {
+        let grid = grid("abc\ndef");
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), b"be");
+        assert_eq!(grid.column(3).count(), 0);
+        let columns: Vec<Vec<u8>> = grid.columns().map(|column| column.copied().collect()).collect();
+        assert_eq!(columns, vec![b"ad".to_vec(), b"be".to_vec(), b"cf".to_vec()]);
+    }
+
+    #[test]
+    fn ragged_rows_are_rejected() {
+        assert_eq!(Grid::from_rows(vec![vec![1, 2], vec![3]]), None);
+        let grid = Grid::from_rows(vec![vec![1, 2], vec![3, 4]]).unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(grid[Pos::new(1, 0)], 3);
+    }
+
+    #[test]
+    fn ragged_lines_are_reported_where_they_end() {
+        let err = "abc\nab\nabc".parse::<Grid<u8>>().unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 3: expected 3 columns, found 2");
+        let err = "ab\nabcd".parse::<Grid<u8>>().unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 3: expected 2 columns, found 4");
+    }
+}
@@ -0,0 +1,168 @@
+use std::fmt;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+use crate::error::{AocError, Result};
+
+/// Environment variable naming a directory holding `day_N_input.txt` files.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Where a puzzle input is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Stdin => write!(f, "<stdin>"),
+            Source::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// The file name of a day's puzzle input, e.g. `day_3_input.txt`.
+pub fn file_name(day: u8) -> String {
+    format!("day_{}_input.txt", day)
+}
+
+/// Works out where the input for `day` should be read from.
+///
+/// In order of preference:
+/// 1. `explicit`, if given: `-` means stdin, anything else is a path used as-is.
+/// 2. `$AOC_INPUT_DIR/day_N_input.txt`.
+/// 3. `assets/day_N_input.txt` relative to this crate's manifest directory.
+///
+/// Only existing files are considered for 2 and 3, so that a missing file in
+/// `AOC_INPUT_DIR` still falls back to the bundled assets.
+pub fn resolve(day: u8, explicit: Option<&str>) -> Result<Source> {
+    let input_dir = env::var_os(INPUT_DIR_VAR).map(PathBuf::from);
+    let assets_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets");
+    resolve_in(day, explicit, input_dir.as_deref(), &assets_dir)
+}
+
+/// [`resolve`], with the `AOC_INPUT_DIR` and assets directories passed in.
+fn resolve_in(day: u8, explicit: Option<&str>, input_dir: Option<&Path>, assets_dir: &Path) -> Result<Source> {
+    match explicit {
+        Some("-") => return Ok(Source::Stdin),
+        Some(path) => return Ok(Source::File(PathBuf::from(path))),
+        None => (),
+    }
+
+    let mut tried = Vec::new();
+    if let Some(dir) = input_dir {
+        tried.push(dir.join(file_name(day)));
+    }
+    tried.push(assets_dir.join(file_name(day)));
+
+    match tried.iter().find(|path| path.is_file()) {
+        Some(path) => Ok(Source::File(path.clone())),
+        None => Err(AocError::InputNotFound { day, tried }),
+    }
+}
+
+/// Reads the whole of `source` into a string.
+pub fn read(source: &Source) -> Result<String> {
+    let result = match source {
+        Source::Stdin => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input).map(|_| input)
+        }
+        Source::File(path) => fs::read_to_string(path),
+    };
+    result.map_err(|error| AocError::Io { input: source.clone(), error })
+}
+
+/// Resolves and reads the input for `day`; see [`resolve`].
+pub fn load(day: u8, explicit: Option<&str>) -> Result<String> {
+    read(&resolve(day, explicit)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    /// A fresh, empty directory under the system temp dir, removed when dropped.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> TempDir {
+            let dir = env::temp_dir().join(format!("aoc_input_{}_{}", name, process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            TempDir(dir)
+        }
+
+        /// Writes `day`'s input into the directory and returns its path.
+        fn with_input(&self, day: u8) -> PathBuf {
+            let path = self.0.join(file_name(day));
+            fs::write(&path, "input").unwrap();
+            path
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn explicit_input_wins_even_if_missing() {
+        let inputs = TempDir::new("explicit_inputs");
+        let assets = TempDir::new("explicit_assets");
+        inputs.with_input(1);
+        assets.with_input(1);
+
+        assert_eq!(resolve_in(1, Some("-"), Some(&inputs.0), &assets.0).unwrap(), Source::Stdin);
+        assert_eq!(
+            resolve_in(1, Some("missing.txt"), Some(&inputs.0), &assets.0).unwrap(),
+            Source::File(PathBuf::from("missing.txt")),
+        );
+    }
+
+    #[test]
+    fn input_dir_is_preferred_over_assets() {
+        let inputs = TempDir::new("preferred_inputs");
+        let assets = TempDir::new("preferred_assets");
+        let input = inputs.with_input(2);
+        let asset = assets.with_input(2);
+        assets.with_input(3);
+
+        assert_eq!(resolve_in(2, None, Some(&inputs.0), &assets.0).unwrap(), Source::File(input));
+        assert_eq!(resolve_in(2, None, None, &assets.0).unwrap(), Source::File(asset));
+        // A day missing from the input dir still falls back to the assets.
+        assert_eq!(
+            resolve_in(3, None, Some(&inputs.0), &assets.0).unwrap(),
+            Source::File(assets.0.join(file_name(3))),
+        );
+    }
+
+    #[test]
+    fn missing_input_lists_every_path_tried() {
+        let inputs = TempDir::new("missing_inputs");
+        let assets = TempDir::new("missing_assets");
+
+        match resolve_in(4, None, Some(&inputs.0), &assets.0) {
+            Err(AocError::InputNotFound { day: 4, tried }) => {
+                assert_eq!(tried, vec![inputs.0.join("day_4_input.txt"), assets.0.join("day_4_input.txt")]);
+            }
+            other => panic!("expected InputNotFound, got {:?}", other),
+        }
+        match resolve_in(4, None, None, &assets.0) {
+            Err(AocError::InputNotFound { tried, .. }) => assert_eq!(tried, vec![assets.0.join("day_4_input.txt")]),
+            other => panic!("expected InputNotFound, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn files_are_read_whole() {
+        let inputs = TempDir::new("read_inputs");
+        let path = inputs.with_input(5);
+        assert_eq!(read(&Source::File(path)).unwrap(), "input");
+        assert!(matches!(read(&Source::File(inputs.0.join("nope.txt"))), Err(AocError::Io { .. })));
+    }
+}
@@ -0,0 +1,22 @@
+//! My solutions to the 2023 Advent of Code.
+//!
+//! Each day's puzzle lives in its own `dayNN` module (e.g. [`day03::gear_ratios`]), exposing the
+//! solver for each part as a plain function along with an implementation of
+//! [`solution::Solution`].  The [`registry`] ties them together so the `advent_of_code` runner can
+//! dispatch to any of them by day and part, checking what they produce against the [`answers`] on
+//! record.
+
+pub mod answers;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+#[cfg(test)]
+mod doc_examples;
+pub mod error;
+pub mod grid;
+pub mod input;
+pub mod registry;
+pub mod solution;
@@ -1,3 +1,167 @@
+use std::path::PathBuf;
+use std::{env, process};
+use std::time::Duration;
+use advent_of_code::answers::{self, Answers, Check};
+use advent_of_code::error::AocError;
+use advent_of_code::input;
+use advent_of_code::registry::{self, Puzzle, PUZZLES};
+
+const USAGE: &str = "Usage: advent_of_code --day <N> [--part <1|2>] [--input <PATH|->] [--answers <PATH>] [--record]
+       advent_of_code --all [--part <1|2>] [--answers <PATH>] [--record]
+
+Inputs are read from --input if given (`-` for stdin), otherwise from
+$AOC_INPUT_DIR/day_N_input.txt, falling back to the crate's assets directory.
+
+Each answer is checked against those recorded for the same input in --answers
+(answers.toml in the crate directory by default) and marked PASS, FAIL or NEW.
+--record adds NEW answers to the file.";
+
+/// Which puzzles and parts were requested on the command line.
+struct Selection {
+    puzzles: Vec<&'static Puzzle>,
+    parts: Vec<u8>,
+    input: Option<String>,
+    answers: PathBuf,
+    record: bool,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Selection, String> {
+    let mut day: Option<u8> = None;
+    let mut part: Option<u8> = None;
+    let mut all = false;
+    let mut input: Option<String> = None;
+    let mut answers = None;
+    let mut record = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" | "-d" => {
+                let value = args.next().ok_or("--day requires a value")?;
+                day = Some(value.parse().map_err(|_| format!("invalid day `{}`", value))?);
+            }
+            "--part" | "-p" => {
+                let value = args.next().ok_or("--part requires a value")?;
+                match value.as_str() {
+                    "1" => part = Some(1),
+                    "2" => part = Some(2),
+                    _ => return Err(format!("invalid part `{}`; expected 1 or 2", value)),
+                }
+            }
+            "--input" | "-i" => input = Some(args.next().ok_or("--input requires a value")?),
+            "--all" | "-a" => all = true,
+            "--answers" => answers = Some(PathBuf::from(args.next().ok_or("--answers requires a value")?)),
+            "--record" => record = true,
+            _ => return Err(format!("unexpected argument `{}`", arg)),
+        }
+    }
+
+    let puzzles = match (day, all) {
+        (Some(_), true) => return Err("--day and --all cannot be used together".to_string()),
+        (None, true) if input.is_some() => {
+            return Err("--input can only be used with --day".to_string())
+        }
+        (None, false) => return Err("either --day or --all is required".to_string()),
+        (None, true) => PUZZLES.iter().collect(),
+        (Some(day), false) => {
+            vec![registry::find(day).ok_or(format!("no puzzle registered for day {}", day))?]
+        }
+    };
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    let answers = answers.unwrap_or_else(answers::default_path);
+
+    Ok(Selection { puzzles, parts, input, answers, record })
+}
+
+/// Runs the requested parts of `puzzle`, printing each answer and how it compares with `answers`.
+/// New answers are added to `answers` if `record` is set.  Returns `false` if the puzzle couldn't
+/// be run, one of its parts failed or an answer differs from the one on record.
+fn run(puzzle: &Puzzle, parts: &[u8], input_arg: Option<&str>, answers: &mut Answers, record: bool) -> bool {
+    let source = match input::resolve(puzzle.day, input_arg) {
+        Ok(source) => source,
+        Err(err) => {
+            eprintln!("Day {}: {}", puzzle.day, err);
+            return false;
+        }
+    };
+    let input = match input::read(&source) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Day {}: {}", puzzle.day, err);
+            return false;
+        }
+    };
+
+    let report = match (puzzle.run)(&input, parts) {
+        Ok(report) => report,
+        Err(err) => {
+            eprintln!("Day {}: unable to parse {}: {}", puzzle.day, source, err);
+            return false;
+        }
+    };
+
+    let hash = answers::input_hash(&input);
+    let mut all_solved = true;
+    for part in report.parts {
+        let time = format_duration(report.parse_time + part.time);
+        match part.answer {
+            Ok(answer) => {
+                let check = answers.check(puzzle.day, &hash, part.part, &answer);
+                println!("Day {} Part {}:\t{}\t({})\t{}", puzzle.day, part.part, answer, time, check);
+                match check {
+                    Check::Fail { .. } => all_solved = false,
+                    Check::New if record => answers.insert(puzzle.day, &hash, part.part, &answer),
+                    _ => {}
+                }
+            }
+            Err(AocError::Unsolved { .. }) => {
+                println!("Day {} Part {}:\tnot yet solved", puzzle.day, part.part)
+            }
+            Err(err) => {
+                eprintln!("Day {} Part {}: {}", puzzle.day, part.part, err);
+                all_solved = false;
+            }
+        }
+    }
+    all_solved
+}
+
+fn format_duration(duration: Duration) -> String {
+    match duration.as_micros() {
+        micros if micros < 1_000 => format!("{}µs", micros),
+        micros if micros < 1_000_000 => format!("{:.2}ms", micros as f64 / 1_000.0),
+        _ => format!("{:.2}s", duration.as_secs_f64()),
+    }
+}
+
 fn main() {
-    println!("Hello, world!");
+    let selection = parse_args(env::args().skip(1)).unwrap_or_else(|err| {
+        eprintln!("error: {}\n\n{}", err, USAGE);
+        process::exit(2);
+    });
+
+    let mut answers = Answers::load(&selection.answers).unwrap_or_else(|err| {
+        eprintln!("{}: {}", selection.answers.display(), err);
+        process::exit(1);
+    });
+    let recorded = answers.clone();
+
+    let mut all_ran = true;
+    for puzzle in selection.puzzles {
+        all_ran &= run(puzzle, &selection.parts, selection.input.as_deref(), &mut answers, selection.record);
+    }
+
+    if answers != recorded {
+        if let Err(err) = answers.save(&selection.answers) {
+            eprintln!("{}", err);
+            all_ran = false;
+        }
+    }
+
+    if !all_ran {
+        process::exit(1);
+    }
 }
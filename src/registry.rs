@@ -0,0 +1,44 @@
+use crate::day01::Trebuchet;
+use crate::day02::CubeConundrum;
+use crate::day03::GearRatios;
+use crate::day04::Scratchcards;
+use crate::day05::SeedFertilizer;
+use crate::day06::WaitForIt;
+use crate::error::Result;
+use crate::solution::{self, Report, Solution};
+
+/// Parses a puzzle input and runs the requested parts against it.
+pub type Runner = fn(&str, &[u8]) -> Result<Report>;
+
+/// A single day's puzzle as seen by the runner.
+pub struct Puzzle {
+    pub day: u8,
+    pub name: &'static str,
+    pub run: Runner,
+}
+
+impl Puzzle {
+    /// Registers the puzzle solved by `S`.
+    pub const fn of<S: Solution>() -> Puzzle {
+        Puzzle {
+            day: S::DAY,
+            name: S::NAME,
+            run: solution::run::<S>,
+        }
+    }
+}
+
+/// Every puzzle known to the runner, ordered by day.
+pub const PUZZLES: &[Puzzle] = &[
+    Puzzle::of::<Trebuchet>(),
+    Puzzle::of::<CubeConundrum>(),
+    Puzzle::of::<GearRatios>(),
+    Puzzle::of::<Scratchcards>(),
+    Puzzle::of::<SeedFertilizer>(),
+    Puzzle::of::<WaitForIt>(),
+];
+
+/// Looks up the puzzle for `day`.
+pub fn find(day: u8) -> Option<&'static Puzzle> {
+    PUZZLES.iter().find(|puzzle| puzzle.day == day)
+}
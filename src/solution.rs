@@ -0,0 +1,84 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+use crate::error::{AocError, Result};
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(u64),
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Number(value as u64)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Answer::Number(value)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+/// A single day's puzzle, split into a parsing stage shared by both parts and the two parts
+/// themselves.
+pub trait Solution {
+    /// The day of December the puzzle was released on.
+    const DAY: u8;
+    /// A short, snake_case name for the puzzle.
+    const NAME: &'static str;
+
+    /// The parsed puzzle input handed to each part.
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part_1(input: &Self::Input) -> Result<Answer>;
+    fn part_2(input: &Self::Input) -> Result<Answer>;
+}
+
+/// The answer to one part, or why there isn't one, along with how long it took to compute.
+#[derive(Debug)]
+pub struct PartReport {
+    pub part: u8,
+    pub answer: Result<Answer>,
+    pub time: Duration,
+}
+
+/// Everything produced by running a [`Solution`] against one input.
+#[derive(Debug)]
+pub struct Report {
+    pub parse_time: Duration,
+    pub parts: Vec<PartReport>,
+}
+
+/// Parses `input` once and then runs each of the requested `parts` (`1` or `2`) of `S` against it,
+/// timing every stage.  Only a failure to parse fails the whole run; each part's own error is kept
+/// in its [`PartReport`].
+pub fn run<S: Solution>(input: &str, parts: &[u8]) -> Result<Report> {
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse_time = start.elapsed();
+
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = match part {
+                1 => S::part_1(&parsed),
+                2 => S::part_2(&parsed),
+                _ => Err(AocError::Unsolved { day: S::DAY, part }),
+            };
+            PartReport { part, answer, time: start.elapsed() }
+        })
+        .collect();
+
+    Ok(Report { parse_time, parts })
+}